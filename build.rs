@@ -3,7 +3,7 @@ use std::process::Command;
 
 fn main() {
     let output = Command::new("cargo")
-        .args(&["tree", "--quiet", "--color", "never"])
+        .args(["tree", "--quiet", "--color", "never"])
         .output()
         .expect("failed to execute `cargo tree`");
 
@@ -0,0 +1,221 @@
+use anyhow::{bail, Context, Result};
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use super::{util, Cmd};
+use crate::{
+    color::{
+        self,
+        adjust::{self, Adjustment},
+        format::NumberFormat,
+        Color, ColorFormat, ColorSpace,
+    },
+    terminal::{self, stdin},
+    State,
+};
+
+const COLOR_HELP_MESSAGE: &str = "\
+The input colors. Multiple colors can be specified. Supported formats:
+
+* HTML color name, e.g. 'rebeccapurple'
+* Hexadecimal RGB color, e.g. '07F', '0077FF'
+* Color components, e.g. 'hsl(30, 100%, 50%)'
+  Commas and parentheses are optional.
+  For supported color spaces, see <https://aloso.github.io/colo/color_spaces>
+
+If colo is used behind a pipe or outside of a terminal, the colors can be provided via stdin, e.g.
+
+$ echo orange blue FF7700 | colo adjust --lighten 10%";
+
+const SPACE_HELP: &str = "\
+The color space in which the colors are adjusted. Amounts are percentages of the lightness \
+and saturation/chroma ranges: 100% is the full lightness or saturation in HSL, a lightness \
+of 100 and a chroma of 150 in LCh, and a lightness of 1 and a chroma of 0.4 in OKLCH";
+
+/// The `adjust` subcommand
+pub struct Adjust {
+    pub colors: Vec<(Color, ColorFormat)>,
+    pub adjustments: Vec<Adjustment>,
+    pub color_space: ColorSpace,
+    pub output: Vec<ColorFormat>,
+    pub number_format: NumberFormat,
+    pub size: u32,
+}
+
+impl Cmd for Adjust {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        let amount = |name: &'a str, value_name: &'a str, help: &'a str| {
+            Arg::with_name(name)
+                .long(name)
+                .takes_value(true)
+                .value_name(value_name)
+                .multiple(true)
+                .number_of_values(1)
+                .allow_hyphen_values(true)
+                .help(help)
+        };
+
+        SubCommand::with_name("adjust")
+            .about("Lighten, darken, saturate, desaturate, rotate or invert colors")
+            .long_about(
+                "Adjust colors by applying one or more operations in the order in which they \
+            are given, e.g. '--lighten 10% --rotate 30 --desaturate 20%'. \
+            Colors that end up outside of the sRGB gamut are mapped into the gamut by \
+            reducing their chroma.",
+            )
+            .args(&[
+                Arg::with_name("colors")
+                    .takes_value(true)
+                    .required(state.interactive)
+                    .help(COLOR_HELP_MESSAGE)
+                    .multiple(true)
+                    .use_delimiter(false),
+                amount("lighten", "amount", "Increase the lightness, e.g. '10%'"),
+                amount("darken", "amount", "Decrease the lightness, e.g. '10%'"),
+                amount(
+                    "saturate",
+                    "amount",
+                    "Increase the saturation or chroma, e.g. '20%'",
+                ),
+                amount(
+                    "desaturate",
+                    "amount",
+                    "Decrease the saturation or chroma, e.g. '20%'",
+                ),
+                amount(
+                    "rotate",
+                    "degrees",
+                    "Rotate the hue by the given angle in degrees, e.g. '30'",
+                ),
+                Arg::with_name("invert")
+                    .long("invert")
+                    .multiple(true)
+                    .help("Invert the red, green and blue channels"),
+                Arg::with_name("space")
+                    .long("space")
+                    .takes_value(true)
+                    .possible_values(&["hsl", "lch", "oklch"])
+                    .case_insensitive(true)
+                    .default_value("hsl")
+                    .help(SPACE_HELP),
+                Arg::with_name("output-format")
+                    .long("out")
+                    .short("o")
+                    .takes_value(true)
+                    .use_delimiter(true)
+                    .possible_values(super::COLOR_FORMATS)
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help(
+                        "Output format (html, hex, color space or code literal). Several \
+                        comma-separated formats can be specified, e.g. 'hex,oklch,rgb'",
+                    ),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+                Arg::with_name("size")
+                    .long("size")
+                    .short("s")
+                    .takes_value(true)
+                    .default_value("4")
+                    .help("Size of the color square in terminal rows"),
+            ])
+            .args(&util::number_format_args())
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let mut colors = match matches.values_of("colors") {
+            Some(values) => util::values_to_colors(values, state)?,
+            None => vec![],
+        };
+
+        if !state.interactive && colors.is_empty() {
+            let input = stdin::read_all()?;
+            colors = color::parse(&input, state)?;
+        }
+
+        // the adjustments are applied in the order of the arguments
+        let mut adjustments = Vec::new();
+        for name in ["lighten", "darken", "saturate", "desaturate", "rotate"] {
+            let (values, indices) = match (matches.values_of(name), matches.indices_of(name)) {
+                (Some(values), Some(indices)) => (values, indices),
+                _ => continue,
+            };
+            for (value, index) in values.zip(indices) {
+                let amount = parse_amount(value)?;
+                let adjustment = match name {
+                    "lighten" => Adjustment::Lighten(amount),
+                    "darken" => Adjustment::Darken(amount),
+                    "saturate" => Adjustment::Saturate(amount),
+                    "desaturate" => Adjustment::Desaturate(amount),
+                    _ => Adjustment::Rotate(amount),
+                };
+                adjustments.push((index, adjustment));
+            }
+        }
+        if let Some(indices) = matches.indices_of("invert") {
+            adjustments.extend(indices.map(|index| (index, Adjustment::Invert)));
+        }
+        if adjustments.is_empty() {
+            bail!("No adjustment was specified, e.g. --lighten 10%");
+        }
+        adjustments.sort_by_key(|&(index, _)| index);
+
+        let color_space = match matches.value_of("space").unwrap().to_lowercase().as_str() {
+            "lch" => ColorSpace::Lch,
+            "oklch" => ColorSpace::Oklch,
+            _ => ColorSpace::Hsl,
+        };
+
+        let mut output = util::get_color_formats(matches, "output-format")?;
+        if output.is_empty() {
+            let format = if colors.is_empty() {
+                None
+            } else if colors.windows(2).all(|c| c[0].1 == c[1].1) {
+                Some(colors[0].1).filter(|&c| c != ColorFormat::Html)
+            } else {
+                None
+            };
+            output.push(format.unwrap_or_default());
+        }
+        let output = output
+            .into_iter()
+            .map(|f| util::css_format(matches, f))
+            .collect();
+
+        Ok(Adjust {
+            colors,
+            adjustments: adjustments.into_iter().map(|(_, a)| a).collect(),
+            color_space,
+            output,
+            number_format: util::get_number_format(matches)?,
+            size: matches
+                .value_of("size")
+                .map(util::parse_size)
+                .unwrap_or(Ok(4))?,
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        terminal::show_colors(
+            state,
+            self.colors
+                .iter()
+                .map(|&(c, _)| adjust::adjust(c, &self.adjustments, self.color_space)),
+            &self.output,
+            self.number_format,
+            None,
+            self.size,
+            false,
+        )
+    }
+}
+
+/// Parses an amount such as '10%' or '-30'
+fn parse_amount(value: &str) -> Result<f64> {
+    value
+        .trim()
+        .trim_end_matches('%')
+        .parse()
+        .with_context(|| format!("Invalid amount {:?}", value))
+}
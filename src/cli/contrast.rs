@@ -3,16 +3,27 @@ use std::iter;
 use anyhow::{bail, Result};
 use clap::{App, Arg, ArgMatches, SubCommand};
 use colored::Colorize;
+use std::{
+    io::{stdout, Write},
+    path::PathBuf,
+};
 
 use super::{util, Cmd};
 use crate::{
-    color::{contrast, space::Rgb, Color, ColorFormat},
-    terminal::{compare_colors, stdin},
+    color::{
+        contrast::{self, ApcaUsage, WcagLevel, APCA_FONT_WEIGHTS},
+        difference::DeltaE,
+        space::Rgb,
+        Algorithm, Color, ColorFormat,
+    },
+    export::{self, Matrix},
+    terminal::{self, compare_colors, json::Json, stdin},
     State,
 };
 
 const COLOR_HELP: &str = "\
-At most 2 colors. If only one color is provided, the other color defaults to white. Supported formats:
+The text color and the background color. If only one color is provided, the background defaults to white. \
+If more than 2 colors are provided, the contrast of each pair is printed as a matrix. Supported formats:
 
 * HTML color name, e.g. 'rebeccapurple'
 * Hexadecimal RGB color, e.g. '07F', '0077FF'
@@ -25,20 +36,40 @@ If colo is used behind a pipe or outside of a terminal, the colors can be provid
 $ echo orange blue | colo contrast
 ";
 
+/// Help message of the `--export` argument
+const EXPORT_HELP: &str = "\
+Write the contrast matrix to a file instead of printing it. Supported file types: .csv, .json";
+
 /// The `contrast` subcommand
 pub struct Contrast {
     pub color1: Color,
     pub color2: Color,
+    pub algorithm: Algorithm,
+    /// The minimum contrast; with APCA, the minimum absolute Lc value
+    pub min: Option<f64>,
+    /// The WCAG success criterion that must be met
+    pub level: Option<WcagLevel>,
+    /// The contrast that `--fix` should reach
+    pub fix: Option<f64>,
+    /// Whether `--fix` changes the background instead of the text color
+    pub fix_background: bool,
+    /// The colors of the contrast matrix, in matrix mode
+    pub matrix: Option<Vec<Color>>,
+    pub export: Option<PathBuf>,
 }
 
 impl Cmd for Contrast {
     fn command<'a, 'b>(state: State) -> App<'a, 'b> {
         SubCommand::with_name("contrast")
-            .about("Get the contrast between two colors")
+            .visible_alias("c")
+            .about("Get the contrast between two or more colors")
             .long_about(
                 "Return the contrast between two colors according to the W3 specification. \
             The contrast is a value between 1 and 21. \
-            Text contrast should always be at least 4.5, or 3 for large text.",
+            Text contrast should always be at least 4.5, or 3 for large text.\n\n\
+            With `--algorithm apca`, the APCA lightness contrast (Lc) of the text on the \
+            background is returned instead, which is negative for light text on a dark \
+            background. Body text should have an Lc of at least 75, or 60 for larger text.",
             )
             .arg(
                 Arg::with_name("colors")
@@ -47,6 +78,71 @@ impl Cmd for Contrast {
                     .multiple(true)
                     .required(state.interactive),
             )
+            .arg(
+                Arg::with_name("algorithm")
+                    .long("algorithm")
+                    .short("a")
+                    .takes_value(true)
+                    .possible_values(&["wcag", "apca"])
+                    .default_value("wcag")
+                    .help("The contrast algorithm: The WCAG 2 contrast ratio, or APCA"),
+            )
+            .arg(
+                Arg::with_name("min")
+                    .long("min")
+                    .takes_value(true)
+                    .value_name("contrast")
+                    .help(
+                        "Fail with a non-zero exit status if the contrast is lower. \
+                        With APCA, this is the minimum absolute Lc value",
+                    ),
+            )
+            .arg(
+                Arg::with_name("level")
+                    .long("level")
+                    .takes_value(true)
+                    .possible_values(&["AA", "AAA", "AA-large", "AAA-large", "non-text"])
+                    .case_insensitive(true)
+                    .help(
+                        "Fail with a non-zero exit status if the WCAG 2 success criterion \
+                        is not met. 'non-text' is for UI components and graphical objects",
+                    ),
+            )
+            .arg(
+                Arg::with_name("fix")
+                    .long("fix")
+                    .takes_value(true)
+                    .value_name("target")
+                    .conflicts_with_all(&["min", "level"])
+                    .help(
+                        "Suggest the closest text color that reaches the target contrast. \
+                        The target is a contrast ratio, a WCAG level such as 'AA' or \
+                        'AAA-large', or with APCA, an Lc value",
+                    ),
+            )
+            .arg(
+                Arg::with_name("fix-background")
+                    .long("fix-background")
+                    .requires("fix")
+                    .help("Change the background color instead of the text color with --fix"),
+            )
+            .arg(
+                Arg::with_name("matrix")
+                    .long("matrix")
+                    .short("m")
+                    .conflicts_with_all(&["min", "level", "fix"])
+                    .help(
+                        "Print the contrast of each pair of colors as a matrix. With APCA, \
+                        the rows are the text colors and the columns the backgrounds",
+                    ),
+            )
+            .arg(
+                Arg::with_name("export")
+                    .long("export")
+                    .takes_value(true)
+                    .value_name("file")
+                    .help(EXPORT_HELP),
+            )
     }
 
     fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
@@ -59,31 +155,110 @@ impl Cmd for Contrast {
             let text = stdin::read_all()?;
             colors = util::values_to_colors(iter::once(text.as_str()), state)?;
         }
-        if colors.len() == 1 {
+        let matrix = matches.is_present("matrix") || colors.len() > 2;
+        if colors.len() == 1 && !matrix {
             let white = Color::Rgb(Rgb::new(255.0, 255.0, 255.0));
             colors.push((white, ColorFormat::Html));
         }
-        if colors.len() != 2 {
-            bail!("Expected 2 colors, got {}", colors.len());
+        if colors.len() < 2 {
+            bail!("Expected at least 2 colors, got {}", colors.len());
+        }
+        if matrix
+            && (matches.is_present("fix")
+                || matches.is_present("min")
+                || matches.is_present("level"))
+        {
+            bail!("--fix, --min and --level can only be used with 2 colors");
+        }
+        let export = util::get_export_path(matches);
+        if export.is_some() && !matrix {
+            bail!("--export can only be used with a contrast matrix");
         }
 
+        let algorithm = match matches.value_of("algorithm") {
+            Some("apca") => Algorithm::Apca,
+            _ => Algorithm::Wcag,
+        };
+        let min = match matches.value_of("min") {
+            Some(min) => match min.parse::<f64>() {
+                Ok(min) if min >= 0.0 => Some(min),
+                _ => bail!("Invalid minimum contrast {:?}", min),
+            },
+            None => None,
+        };
+        let level = matches.value_of("level").and_then(WcagLevel::from_name);
+        if level.is_some() && algorithm == Algorithm::Apca {
+            bail!("--level can't be used with APCA, use --min instead");
+        }
+
+        let fix = match matches.value_of("fix") {
+            Some(target) => match (target.parse::<f64>(), WcagLevel::from_name(target)) {
+                (Ok(target), _) if target >= 0.0 => Some(target),
+                (_, Some(level)) if algorithm == Algorithm::Wcag => Some(level.min_contrast()),
+                _ => bail!("Invalid target contrast {:?}", target),
+            },
+            None => None,
+        };
+
         Ok(Contrast {
             color1: colors[0].0,
             color2: colors[1].0,
+            algorithm,
+            min,
+            level,
+            fix,
+            fix_background: matches.is_present("fix-background"),
+            matrix: if matrix {
+                Some(colors.iter().map(|&(c, _)| c).collect())
+            } else {
+                None
+            },
+            export,
         })
     }
 
     fn run(&self, state: State) -> Result<()> {
+        if let Some(colors) = &self.matrix {
+            return self.run_matrix(state, colors);
+        }
+        if let Some(target) = self.fix {
+            return self.run_fix(state, target);
+        }
+        match self.algorithm {
+            Algorithm::Wcag => self.run_wcag(state)?,
+            Algorithm::Apca => self.run_apca(state)?,
+        }
+        self.check(self.algorithm.contrast(self.color1, self.color2))
+    }
+}
+
+impl Contrast {
+    fn run_wcag(&self, state: State) -> Result<()> {
         let lum1 = self.color1.relative_luminance();
         let lum2 = self.color2.relative_luminance();
-        let contrast = contrast(lum1, lum2);
+        let contrast = contrast::contrast(lum1, lum2);
 
-        let level = match contrast {
-            _ if contrast < 3.0 => colored::Color::Red,
-            _ if contrast < 4.5 => colored::Color::Yellow,
-            _ if contrast < 6.0 => colored::Color::BrightWhite,
-            _ => colored::Color::Green,
-        };
+        if state.json {
+            return Json::Object(vec![
+                ("color1", self.color1.into()),
+                ("color2", self.color2.into()),
+                ("luminance1", lum1.into()),
+                ("luminance2", lum2.into()),
+                ("contrast", contrast.into()),
+                (
+                    "wcag",
+                    Json::Object(
+                        WcagLevel::ALL
+                            .iter()
+                            .map(|&level| (level.name(), (contrast >= level.min_contrast()).into()))
+                            .collect(),
+                    ),
+                ),
+            ])
+            .print();
+        }
+
+        let level = rating(Algorithm::Wcag, contrast);
 
         compare_colors(
             state,
@@ -91,6 +266,258 @@ impl Cmd for Contrast {
             self.color2,
             format!("{:.2}", contrast).color(level),
             &format!("(relative luminance: {:.3} / {:.3})", lum1, lum2),
+        )?;
+        print_wcag_report(state, contrast)
+    }
+
+    /// Prints the contrast of each pair of colors. With APCA, the rows are the
+    /// text colors and the columns are the background colors.
+    fn run_matrix(&self, state: State, colors: &[Color]) -> Result<()> {
+        let values: Vec<Vec<f64>> = colors
+            .iter()
+            .map(|&text| {
+                colors
+                    .iter()
+                    .map(|&background| self.algorithm.contrast(text, background))
+                    .collect()
+            })
+            .collect();
+        let matrix = Matrix {
+            colors,
+            values: &values,
+            name: "contrast",
+        };
+
+        if let Some(path) = &self.export {
+            return export::export_matrix(path, &matrix);
+        }
+        if state.json {
+            let algorithm = match self.algorithm {
+                Algorithm::Wcag => "wcag",
+                Algorithm::Apca => "apca",
+            };
+            let rows = values
+                .iter()
+                .map(|row| Json::Array(row.iter().map(|&v| v.into()).collect()))
+                .collect();
+            return Json::Object(vec![
+                (
+                    "colors",
+                    Json::Array(colors.iter().map(|&c| c.into()).collect()),
+                ),
+                ("algorithm", algorithm.into()),
+                ("contrast", Json::Array(rows)),
+            ])
+            .print();
+        }
+
+        let precision = match self.algorithm {
+            Algorithm::Wcag => 2,
+            Algorithm::Apca => 1,
+        };
+        terminal::print_matrix(state, colors, &values, precision, |value| {
+            rating(self.algorithm, value)
+        })
+    }
+
+    /// Prints the closest color that reaches the target contrast
+    fn run_fix(&self, state: State, target: f64) -> Result<()> {
+        let (original, changed) = if self.fix_background {
+            (self.color2, "background")
+        } else {
+            (self.color1, "text color")
+        };
+        let fixed = match contrast::fix_contrast(
+            self.algorithm,
+            self.color1,
+            self.color2,
+            target,
+            self.fix_background,
+        ) {
+            Some(fixed) => fixed,
+            None => bail!(
+                "The contrast {} can't be reached by changing the {}",
+                target,
+                changed
+            ),
+        };
+        let (text, background) = if self.fix_background {
+            (self.color1, fixed)
+        } else {
+            (fixed, self.color2)
+        };
+        let before = self.algorithm.contrast(self.color1, self.color2);
+        let after = self.algorithm.contrast(text, background);
+        let difference = DeltaE::Ciede2000.difference(original, fixed).total;
+
+        if state.json {
+            return Json::Object(vec![
+                ("text", self.color1.into()),
+                ("background", self.color2.into()),
+                ("fixed", changed.into()),
+                ("color", fixed.into()),
+                ("contrast_before", before.into()),
+                ("contrast_after", after.into()),
+                ("difference", difference.into()),
+            ])
+            .print();
+        }
+
+        let precision = match self.algorithm {
+            Algorithm::Wcag => 2,
+            Algorithm::Apca => 1,
+        };
+        compare_colors(
+            state,
+            original,
+            fixed,
+            ColorFormat::Hex.format_or_hex(fixed).bold(),
+            &format!(
+                "(was {}: contrast {:.*} → {:.*}, ΔE2000 {:.2})",
+                ColorFormat::Hex.format_or_hex(original),
+                precision,
+                before,
+                precision,
+                after,
+                difference,
+            ),
+        )
+    }
+
+    /// Returns an error if the contrast doesn't meet the `--min` or `--level`
+    /// requirement
+    fn check(&self, contrast: f64) -> Result<()> {
+        if let Some(min) = self.min {
+            if contrast.abs() < min {
+                bail!(
+                    "The contrast {:.2} is below the minimum of {}",
+                    contrast,
+                    min
+                );
+            }
+        }
+        if let Some(level) = self.level {
+            if contrast < level.min_contrast() {
+                bail!(
+                    "The contrast {:.2} doesn't meet WCAG {} (minimum {})",
+                    contrast,
+                    level.name(),
+                    level.min_contrast()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn run_apca(&self, state: State) -> Result<()> {
+        let lc = Algorithm::Apca.contrast(self.color1, self.color2);
+        let usage = contrast::apca_usage(lc);
+
+        if state.json {
+            let font_sizes = match usage {
+                ApcaUsage::Text(sizes) => APCA_FONT_WEIGHTS
+                    .iter()
+                    .zip(sizes)
+                    .filter_map(|(&weight, size)| {
+                        Some(Json::Object(vec![
+                            ("weight", (weight as usize).into()),
+                            ("size", size?.into()),
+                        ]))
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+            return Json::Object(vec![
+                ("text", self.color1.into()),
+                ("background", self.color2.into()),
+                ("algorithm", "apca".into()),
+                ("contrast", lc.into()),
+                ("non_text", (usage != ApcaUsage::Insufficient).into()),
+                ("font_sizes", Json::Array(font_sizes)),
+            ])
+            .print();
+        }
+
+        let level = rating(Algorithm::Apca, lc);
+
+        let guidance = match usage {
+            ApcaUsage::Text(sizes) => {
+                let sizes: Vec<String> = APCA_FONT_WEIGHTS
+                    .iter()
+                    .zip(sizes)
+                    .filter(|&(&weight, _)| weight == 400 || weight == 700)
+                    .filter_map(|(weight, size)| Some(format!("{}px at {}", size?, weight)))
+                    .collect();
+                format!("min. font size {}", sizes.join(", "))
+            }
+            ApcaUsage::NonText => "non-text elements only".into(),
+            ApcaUsage::Insufficient => "insufficient for text and non-text elements".into(),
+        };
+
+        compare_colors(
+            state,
+            self.color1,
+            self.color2,
+            format!("Lc {:.1}", lc + 0.0).color(level),
+            &format!("(APCA, text on background: {})", guidance),
         )
     }
 }
+
+/// Returns the color for a contrast value, depending on the algorithm
+fn rating(algorithm: Algorithm, contrast: f64) -> colored::Color {
+    match algorithm {
+        Algorithm::Wcag => match contrast {
+            _ if contrast < 3.0 => colored::Color::Red,
+            _ if contrast < 4.5 => colored::Color::Yellow,
+            _ if contrast < 6.0 => colored::Color::BrightWhite,
+            _ => colored::Color::Green,
+        },
+        // the levels recommended for body text, other content text,
+        // headlines and non-text elements
+        Algorithm::Apca => match contrast.abs() {
+            l if l < 45.0 => colored::Color::Red,
+            l if l < 60.0 => colored::Color::Yellow,
+            l if l < 75.0 => colored::Color::BrightWhite,
+            _ => colored::Color::Green,
+        },
+    }
+}
+
+/// Prints which WCAG 2 success criteria are met, as a table
+fn print_wcag_report(state: State, contrast: f64) -> Result<()> {
+    let mut stdout = stdout();
+    let cell = |level: WcagLevel| {
+        let min = level.min_contrast();
+        let (mark, color) = if contrast >= min {
+            ("✓", colored::Color::Green)
+        } else {
+            ("✗", colored::Color::Red)
+        };
+        let cell = format!("{:8}", format!("{} {}", mark, min));
+        if state.color {
+            cell.color(color).to_string()
+        } else {
+            cell
+        }
+    };
+
+    writeln!(stdout)?;
+    writeln!(stdout, " {:14}{:8}AAA", "WCAG 2", "AA")?;
+    writeln!(
+        stdout,
+        " {:14}{}{}",
+        "normal text",
+        cell(WcagLevel::Aa),
+        cell(WcagLevel::Aaa),
+    )?;
+    writeln!(
+        stdout,
+        " {:14}{}{}",
+        "large text",
+        cell(WcagLevel::AaLarge),
+        cell(WcagLevel::AaaLarge),
+    )?;
+    writeln!(stdout, " {:14}{}", "non-text", cell(WcagLevel::NonText))?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::Colorize;
+
+use super::{util, Cmd};
+use crate::{
+    color::{
+        self,
+        cvd::{Model, Simulation},
+        format::NumberFormat,
+        Color, ColorFormat,
+    },
+    terminal::{self, json::Json, stdin},
+    State,
+};
+
+const COLOR_HELP_MESSAGE: &str = "\
+The input colors. Multiple colors can be specified. Supported formats:
+
+* HTML color name, e.g. 'rebeccapurple'
+* Hexadecimal RGB color, e.g. '07F', '0077FF'
+* Color components, e.g. 'hsl(30, 100%, 50%)'
+  Commas and parentheses are optional.
+  For supported color spaces, see <https://aloso.github.io/colo/color_spaces>
+
+If colo is used behind a pipe or outside of a terminal, the colors can be provided via stdin, e.g.
+
+$ echo orange blue FF7700 | colo cvd -t deuteranopia";
+
+/// The `cvd` subcommand
+pub struct Cvd {
+    pub colors: Vec<Color>,
+    pub simulations: Vec<Simulation>,
+    pub output: Vec<ColorFormat>,
+    pub number_format: NumberFormat,
+    pub size: u32,
+}
+
+impl Cmd for Cvd {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        let [types, model, severity] = util::simulation_args();
+        SubCommand::with_name("cvd")
+            .about("Simulate color vision deficiencies")
+            .long_about(
+                "Show how colors are perceived with color vision deficiencies (color blindness). \
+            The original and the simulated colors are shown side by side. \
+            Outside of a terminal, only the simulated colors are printed.",
+            )
+            .args(&[
+                Arg::with_name("colors")
+                    .takes_value(true)
+                    .required(state.interactive)
+                    .help(COLOR_HELP_MESSAGE)
+                    .multiple(true)
+                    .use_delimiter(false),
+                Arg::with_name("output-format")
+                    .long("out")
+                    .short("o")
+                    .takes_value(true)
+                    .use_delimiter(true)
+                    .possible_values(super::COLOR_FORMATS)
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help(
+                        "Output format of the simulated colors (html, hex, color space or \
+                        code literal). Several comma-separated formats can be specified",
+                    ),
+                Arg::with_name("size")
+                    .long("size")
+                    .short("s")
+                    .takes_value(true)
+                    .default_value("4")
+                    .help("Size of the color squares in terminal rows"),
+            ])
+            .args(&[types, model.short("m"), severity])
+            .args(&util::number_format_args())
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let mut colors = match matches.values_of("colors") {
+            Some(values) => util::values_to_colors(values, state)?,
+            None => vec![],
+        };
+
+        if !state.interactive && colors.is_empty() {
+            let input = stdin::read_all()?;
+            colors = color::parse(&input, state)?;
+        }
+
+        let simulations = util::get_simulations(matches)?;
+
+        let mut output = util::get_color_formats(matches, "output-format")?;
+        if output.is_empty() {
+            output.push(ColorFormat::Hex);
+        }
+
+        Ok(Cvd {
+            colors: colors.into_iter().map(|(c, _)| c).collect(),
+            simulations,
+            output,
+            number_format: util::get_number_format(matches)?,
+            size: matches
+                .value_of("size")
+                .map(util::parse_size)
+                .unwrap_or(Ok(4))?,
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        for simulation in &self.simulations {
+            if state.json {
+                let model = match simulation.model {
+                    Model::Brettel => "brettel",
+                    Model::Machado => "machado",
+                };
+                for &color in &self.colors {
+                    Json::Object(vec![
+                        ("type", simulation.to_string().into()),
+                        ("model", model.into()),
+                        ("severity", simulation.severity.into()),
+                        ("color", color.into()),
+                        ("simulated", simulation.simulate(color).into()),
+                    ])
+                    .print()?;
+                }
+                continue;
+            }
+
+            if state.color {
+                println!("{}", simulation.to_string().bold());
+            }
+            terminal::show_color_pairs(
+                state,
+                self.colors.iter().map(|&c| (c, simulation.simulate(c))),
+                &self.output,
+                self.number_format,
+                self.size,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::iter;
+
+use anyhow::{bail, Result};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::Colorize;
+
+use super::{util, Cmd};
+use crate::{
+    color::{difference::DeltaE, Color},
+    terminal::{compare_colors, json::Json, stdin},
+    State,
+};
+
+const COLOR_HELP: &str = "\
+Two or more colors. The first color is compared with each of the other colors. Supported formats:
+
+* HTML color name, e.g. 'rebeccapurple'
+* Hexadecimal RGB color, e.g. '07F', '0077FF'
+* Color components, e.g. 'hsl(30, 100%, 50%)'
+  Commas and parentheses are optional.
+  For supported color spaces, see <https://aloso.github.io/colo/color_spaces>
+
+If colo is used behind a pipe or outside of a terminal, the colors can be provided via stdin, e.g.
+
+$ echo orange darkorange | colo diff
+";
+
+/// The `diff` subcommand
+pub struct Diff {
+    pub colors: Vec<Color>,
+    pub metric: DeltaE,
+    pub tolerance: f64,
+}
+
+impl Cmd for Diff {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        SubCommand::with_name("diff")
+            .about("Get the perceptual difference between colors")
+            .long_about(
+                "Return the perceptual difference (ΔE) between colors, \
+            broken down into lightness, chroma and hue. \
+            A difference below the tolerance is considered imperceptible. \
+            The default tolerance is about one just noticeable difference.",
+            )
+            .arg(
+                Arg::with_name("colors")
+                    .help(COLOR_HELP)
+                    .index(1)
+                    .multiple(true)
+                    .required(state.interactive),
+            )
+            .arg(util::metric_arg())
+            .arg(
+                Arg::with_name("tolerance")
+                    .long("tolerance")
+                    .short("t")
+                    .takes_value(true)
+                    .help("The largest difference that is accepted (default: 2.3 for cie76, 0.02 for ok, otherwise 1)"),
+            )
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let mut colors = match matches.values_of("colors") {
+            Some(values) => util::values_to_colors(values, state)?,
+            None => vec![],
+        };
+
+        if !state.interactive && colors.is_empty() {
+            let text = stdin::read_all()?;
+            colors = util::values_to_colors(iter::once(text.as_str()), state)?;
+        }
+        if colors.len() < 2 {
+            bail!("Expected at least 2 colors, got {}", colors.len());
+        }
+
+        let metric = util::get_metric(matches)?;
+        let tolerance = match matches.value_of("tolerance") {
+            Some(t) => match t.parse::<f64>() {
+                Ok(t) if t >= 0.0 => t,
+                _ => bail!("Invalid tolerance {:?}, expected a positive number", t),
+            },
+            None => metric.just_noticeable(),
+        };
+
+        Ok(Diff {
+            colors: colors.into_iter().map(|(c, _)| c).collect(),
+            metric,
+            tolerance,
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        let reference = self.colors[0];
+        // ΔEOK is much smaller than the other metrics
+        let precision = if self.metric == DeltaE::Ok { 4 } else { 2 };
+
+        for &color in &self.colors[1..] {
+            let diff = self.metric.difference(reference, color);
+            let pass = diff.total <= self.tolerance;
+
+            if state.json {
+                Json::Object(vec![
+                    ("color1", reference.into()),
+                    ("color2", color.into()),
+                    ("metric", self.metric.name().into()),
+                    ("difference", diff.total.into()),
+                    ("lightness", diff.lightness.into()),
+                    ("chroma", diff.chroma.into()),
+                    ("hue", diff.hue.into()),
+                    ("tolerance", self.tolerance.into()),
+                    ("pass", pass.into()),
+                ])
+                .print()?;
+                continue;
+            }
+
+            let round = |n: f64| {
+                let factor = 10f64.powi(precision as i32);
+                format!("{:+.*}", precision, (n * factor).round() / factor + 0.0)
+            };
+            if !state.color {
+                println!(
+                    "{:.*}\t{}\t{}\t{}\t{}",
+                    precision,
+                    diff.total,
+                    if pass { "pass" } else { "fail" },
+                    round(diff.lightness),
+                    round(diff.chroma),
+                    round(diff.hue),
+                );
+                continue;
+            }
+
+            let line1 = format!(
+                "{} {:.*}  {}",
+                self.metric.name(),
+                precision,
+                diff.total,
+                if pass { "pass" } else { "fail" },
+            );
+            let line1 = if pass { line1.green() } else { line1.red() };
+            compare_colors(
+                state,
+                reference,
+                color,
+                line1,
+                &format!(
+                    "ΔL {}  ΔC {}  ΔH {}  (tolerance: {})",
+                    round(diff.lightness),
+                    round(diff.chroma),
+                    round(diff.hue),
+                    self.tolerance,
+                ),
+            )?;
+        }
+        Ok(())
+    }
+}
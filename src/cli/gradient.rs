@@ -1,11 +1,16 @@
 use anyhow::{bail, Result};
 use clap::{Arg, SubCommand};
 use console::Term;
+use std::{path::PathBuf, slice};
 
 use super::{util, Cmd};
 use crate::{
-    color::{self, Color, ColorFormat, ColorSpace},
-    terminal::{self, stdin},
+    color::{
+        self, cvd::Simulation, format::NumberFormat, latex::Colormap, template::Template, Color,
+        ColorFormat, ColorSpace,
+    },
+    export::{self, ImageOptions, Sheet, Swatch},
+    terminal::{self, json::Json, stdin},
     State,
 };
 
@@ -13,7 +18,14 @@ pub struct Gradient {
     colors: Vec<(Color, ColorFormat)>,
     color_space: ColorSpace,
     output: ColorFormat,
+    template: Option<Template>,
+    number_format: NumberFormat,
     color_num: Option<usize>,
+    colormap: Option<Colormap>,
+    export: Option<PathBuf>,
+    image: ImageOptions,
+    /// The colors before daltonization
+    original: Option<(Simulation, Vec<Color>)>,
 }
 
 const COLOR_HELP_MESSAGE: &str = "\
@@ -32,6 +44,7 @@ $ echo orange blue FF7700 | colo gradient";
 impl Cmd for Gradient {
     fn command<'a, 'b>(state: State) -> clap::App<'a, 'b> {
         SubCommand::with_name("gradient")
+            .visible_alias("g")
             .about("Create a gradient between colors")
             .args(&[
                 Arg::with_name("colors")
@@ -58,6 +71,7 @@ impl Cmd for Gradient {
                         "hunterlab",
                         "xyz",
                         "yxy",
+                        "oklab",
                     ])
                     .case_insensitive(true)
                     .default_value("lab"),
@@ -69,15 +83,41 @@ impl Cmd for Gradient {
                     .hide_possible_values(true)
                     .case_insensitive(true)
                     .help(
-                        "Output format (html, hex, or color space) [possible values: rgb, cmy, \
-                        cmyk, hsv, hsl, lch, luv, lab, hunterlab, xyz, yxy, gry, hex, html]",
+                        "Output format (html, hex, color space or code literal) \
+                        [possible values: rgb, cmy, cmyk, hsv, hsl, lch, luv, lab, hunterlab, \
+                        xyz, yxy, oklab, oklch, gry, hex, html, nearest, rust, c, swift, kotlin, \
+                        flutter, qt, latex, latex-rgb, p3]",
                     ),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+                Arg::with_name("format")
+                    .long("format")
+                    .short("f")
+                    .takes_value(true)
+                    .help(super::TEMPLATE_HELP),
                 Arg::with_name("steps")
                     .long("steps")
                     .short("s")
                     .takes_value(true)
                     .help("Number of color steps, defaults to 10"),
+                Arg::with_name("colormap")
+                    .long("colormap")
+                    .takes_value(true)
+                    .possible_values(&["tikz", "pgfplots"])
+                    .case_insensitive(true)
+                    .help("Print the gradient as a TikZ shading or a pgfplots colormap for LaTeX"),
             ])
+            .arg(util::daltonize_arg())
+            .arg(util::export_arg())
+            .args(&util::image_size_args())
+            .arg(
+                Arg::with_name("dither")
+                    .long("dither")
+                    .help("Use ordered dithering when exporting the gradient as an image"),
+            )
+            .args(&util::number_format_args())
     }
 
     fn parse(matches: &clap::ArgMatches, &mut state: &mut State) -> Result<Self> {
@@ -112,6 +152,7 @@ impl Cmd for Gradient {
                 }
             })
             .unwrap_or_default();
+        let output = util::css_format(matches, output);
 
         let color_num = matches
             .value_of("steps")
@@ -124,11 +165,34 @@ impl Cmd for Gradient {
             })
             .transpose()?;
 
+        let template = util::get_template(matches, "format")?;
+
+        let original = util::get_daltonize(matches).map(|simulation| {
+            let original = colors.iter().map(|&(c, _)| c).collect();
+            for (color, _) in &mut colors {
+                *color = simulation.daltonize(*color);
+            }
+            (simulation, original)
+        });
+        let number_format = util::get_number_format(matches)?;
+        let colormap = matches
+            .value_of("colormap")
+            .map(|s| match s.to_lowercase().as_str() {
+                "tikz" => Colormap::Tikz,
+                _ => Colormap::Pgfplots,
+            });
+
         Ok(Gradient {
             colors,
             color_space,
             output,
+            template,
+            number_format,
             color_num,
+            colormap,
+            export: util::get_export_path(matches),
+            image: util::get_image_options(matches)?,
+            original,
         })
     }
 
@@ -145,7 +209,7 @@ impl Cmd for Gradient {
         };
 
         let color_steps = self.color_num.unwrap_or_else(|| {
-            if state.color {
+            if state.color && self.export.is_none() && self.colormap.is_none() {
                 (get_term_width() * 2) - 1
             } else {
                 10
@@ -153,7 +217,43 @@ impl Cmd for Gradient {
         });
         let (c1, c2) = (self.colors[0].0, self.colors[1].0);
 
-        if state.color {
+        if let Some(path) = &self.export {
+            let swatches: Vec<Swatch> = (0..=color_steps)
+                .map(|i| {
+                    let ratio = (i as f64) / (color_steps as f64);
+                    Swatch::new(c1.mix_with(c2, self.color_space, ratio))
+                })
+                .collect();
+            let sheet = Sheet {
+                swatches: &swatches,
+                formats: slice::from_ref(&self.output),
+                number_format: self.number_format,
+                image: self.image,
+            };
+            export::export_gradient(path, &sheet, |ratio| {
+                c1.mix_with(c2, self.color_space, ratio)
+            })?;
+        } else if let Some(colormap) = self.colormap {
+            let colors: Vec<Color> = (0..=color_steps)
+                .map(|i| {
+                    let ratio = (i as f64) / (color_steps as f64);
+                    c1.mix_with(c2, self.color_space, ratio)
+                })
+                .collect();
+            println!("{}", colormap.format("colo", &colors));
+        } else if state.json {
+            for i in 0..=color_steps {
+                let ratio = (i as f64) / (color_steps as f64);
+                let color = c1.mix_with(c2, self.color_space, ratio);
+                Json::Object(vec![("position", ratio.into()), ("color", color.into())]).print()?;
+            }
+        } else if let Some(template) = &self.template {
+            let colors = (0..=color_steps).map(|i| {
+                let ratio = (i as f64) / (color_steps as f64);
+                c1.mix_with(c2, self.color_space, ratio)
+            });
+            terminal::print_template(state, colors, template)?;
+        } else if state.color {
             let w = get_term_width();
             terminal::list_small(
                 state,
@@ -163,15 +263,24 @@ impl Cmd for Gradient {
                     let color = c1.mix_with(c2, self.color_space, ratio);
                     (color, self.output)
                 }),
+                self.number_format,
                 2 * w / (color_steps + 1),
             )?;
         } else {
             for i in 0..=color_steps {
                 let ratio = (i as f64) / (color_steps as f64);
                 let color = c1.mix_with(c2, self.color_space, ratio);
-                println!("{}", self.output.format_or_hex(color));
+                println!(
+                    "{}",
+                    self.output.format_or_hex_with(color, self.number_format)
+                );
             }
         }
+
+        if let Some((simulation, original)) = &self.original {
+            let colors = [c1, c2];
+            terminal::daltonize_report(state, *simulation, original, &colors)?;
+        }
         Ok(())
     }
 }
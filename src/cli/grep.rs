@@ -0,0 +1,193 @@
+use anyhow::{bail, Context, Result};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use color_space::ToRgb;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{util, Cmd};
+use crate::{
+    color::{
+        hex,
+        scan::{self, ScanOptions},
+        ColorFormat,
+    },
+    terminal::{self, stdin},
+    State,
+};
+
+const FILES_HELP: &str = "\
+The files to search. Directories are searched recursively, skipping hidden files. \
+If colo is used behind a pipe or outside of a terminal, the text can be provided via stdin, e.g.
+
+$ cat style.css | colo grep";
+
+/// The `grep` subcommand
+pub struct Grep {
+    files: Vec<PathBuf>,
+    stdin: Option<String>,
+    output: ColorFormat,
+    options: ScanOptions,
+    unique: bool,
+}
+
+impl Cmd for Grep {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        SubCommand::with_name("grep")
+            .about("Find colors in text files")
+            .long_about(
+                "Find colors in text files. Recognized are hex colors with a leading '#', \
+                HTML color names and color functions such as 'rgb(255 119 0)' or \
+                'hsl(30, 100%, 50%)'. Each color is printed with its location.",
+            )
+            .args(&[
+                Arg::with_name("files")
+                    .takes_value(true)
+                    .index(1)
+                    .required(state.interactive)
+                    .multiple(true)
+                    .help(FILES_HELP),
+                Arg::with_name("output-format")
+                    .long("out")
+                    .short("o")
+                    .takes_value(true)
+                    .possible_values(super::COLOR_FORMATS)
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help(
+                        "Output format (html, hex, color space or code literal) \
+                        [possible values: rgb, cmy, cmyk, hsv, hsl, lch, luv, lab, hunterlab, \
+                        xyz, yxy, oklab, oklch, gry, hex, html, nearest, rust, c, swift, kotlin, \
+                        flutter, qt, latex, latex-rgb, p3]",
+                    ),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+                Arg::with_name("unique").long("unique").short("u").help(
+                    "Print each distinct color only once, together with the number of \
+                    occurrences. The most frequent colors are printed first",
+                ),
+                Arg::with_name("no-names")
+                    .long("no-names")
+                    .help("Don't recognize HTML color names such as 'red'"),
+            ])
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let files: Vec<PathBuf> = matches
+            .values_of_os("files")
+            .map(|values| values.map(PathBuf::from).collect())
+            .unwrap_or_default();
+
+        let stdin = if !state.interactive && files.is_empty() {
+            Some(stdin::read_all()?)
+        } else {
+            None
+        };
+
+        let output = util::get_color_format(matches, "output-format")?.unwrap_or_default();
+        let output = util::css_format(matches, output);
+
+        Ok(Grep {
+            files,
+            stdin,
+            output,
+            options: ScanOptions {
+                names: !matches.is_present("no-names"),
+            },
+            unique: matches.is_present("unique"),
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        let sources = read_sources(self.stdin.as_deref(), &self.files, |_| true)?;
+
+        if self.unique {
+            // (hex value, color, number of occurrences)
+            let mut groups: Vec<(u32, _, usize)> = Vec::new();
+            for (_, text) in &sources {
+                for m in scan::find_colors(text, self.options) {
+                    let key = hex::rgb_to_u32(m.color.to_rgb());
+                    match groups.iter_mut().find(|(k, ..)| *k == key) {
+                        Some((_, _, count)) => *count += 1,
+                        None => groups.push((key, m.color, 1)),
+                    }
+                }
+            }
+            groups.sort_by(|(.., l), (.., r)| r.cmp(l));
+
+            let groups = groups.into_iter().map(|(_, color, count)| (color, count));
+            terminal::print_color_counts(state, groups, self.output)
+        } else {
+            for (path, text) in &sources {
+                let matches = scan::find_colors(text, self.options);
+                terminal::print_matches(state, path, &matches, self.output)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Returns the path and content of each source: stdin (with the path `-`),
+/// the given files, and the text files in the given directories (recursively)
+/// for which `include` returns true
+pub(super) fn read_sources(
+    stdin: Option<&str>,
+    paths: &[PathBuf],
+    include: impl Fn(&Path) -> bool,
+) -> Result<Vec<(String, String)>> {
+    let mut sources = Vec::new();
+    if let Some(text) = stdin {
+        sources.push(("-".to_string(), text.to_string()));
+    }
+    for path in paths {
+        if path.is_dir() {
+            let mut files = Vec::new();
+            collect_files(path, &mut files)?;
+            for file in files.iter().filter(|f| include(f)) {
+                if let Some(text) = read_text_file(file)? {
+                    sources.push((file.display().to_string(), text));
+                }
+            }
+        } else if path.exists() {
+            let text = read_text_file(path)?.unwrap_or_default();
+            sources.push((path.display().to_string(), text));
+        } else {
+            bail!("The file {:?} does not exist", path);
+        }
+    }
+    Ok(sources)
+}
+
+/// Collects all non-hidden files in a directory recursively
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("Could not read directory {:?}", dir))?
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Reads a file. If it contains a null byte, it is considered binary and
+/// `None` is returned.
+fn read_text_file(path: &Path) -> Result<Option<String>> {
+    let bytes = fs::read(path).with_context(|| format!("Could not read file {:?}", path))?;
+    if bytes.contains(&0) {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
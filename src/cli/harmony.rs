@@ -0,0 +1,212 @@
+use anyhow::{bail, Context, Result};
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use super::{util, Cmd};
+use crate::{
+    color::{self, format::NumberFormat, harmony::Scheme, Color, ColorFormat, ColorSpace},
+    terminal::{self, json::Json, stdin},
+    State,
+};
+
+const COLOR_HELP_MESSAGE: &str = "\
+The base colors. Multiple colors can be specified. Supported formats:
+
+* HTML color name, e.g. 'rebeccapurple'
+* Hexadecimal RGB color, e.g. '07F', '0077FF'
+* Color components, e.g. 'hsl(30, 100%, 50%)'
+  Commas and parentheses are optional.
+  For supported color spaces, see <https://aloso.github.io/colo/color_spaces>
+
+If colo is used behind a pipe or outside of a terminal, the colors can be provided via stdin, e.g.
+
+$ echo orange | colo harmony triadic";
+
+const SCHEME_HELP: &str = "\
+The color scheme:
+
+* complementary: the opposite color
+* split-complementary: the two colors adjacent to the opposite color
+* triadic: three colors evenly spaced around the color wheel
+* tetradic: two pairs of complementary colors forming a rectangle
+* square: four colors evenly spaced around the color wheel
+* analogous: the neighboring colors on both sides";
+
+/// The `harmony` subcommand
+pub struct Harmony {
+    pub colors: Vec<(Color, ColorFormat)>,
+    pub scheme: Scheme,
+    pub spread: f64,
+    pub color_space: ColorSpace,
+    pub output: Vec<ColorFormat>,
+    pub number_format: NumberFormat,
+    pub size: u32,
+}
+
+impl Cmd for Harmony {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        SubCommand::with_name("harmony")
+            .about("Get harmonious colors for a base color")
+            .long_about(
+                "Return the colors of a color scheme such as complementary or triadic, \
+            by rotating the hue of the base color.",
+            )
+            .args(&[
+                Arg::with_name("scheme")
+                    .index(1)
+                    .required(true)
+                    .possible_values(&[
+                        "complementary",
+                        "split-complementary",
+                        "triadic",
+                        "tetradic",
+                        "square",
+                        "analogous",
+                    ])
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help(SCHEME_HELP),
+                Arg::with_name("colors")
+                    .index(2)
+                    .takes_value(true)
+                    .required(state.interactive)
+                    .help(COLOR_HELP_MESSAGE)
+                    .multiple(true)
+                    .use_delimiter(false),
+                Arg::with_name("spread")
+                    .long("spread")
+                    .takes_value(true)
+                    .value_name("degrees")
+                    .default_value("30")
+                    .help(
+                        "The distance between neighbors in analogous schemes, and the \
+                        distance from the opposite color in split-complementary and \
+                        tetradic schemes",
+                    ),
+                Arg::with_name("space")
+                    .long("space")
+                    .takes_value(true)
+                    .possible_values(&["hsl", "lch", "oklch"])
+                    .case_insensitive(true)
+                    .default_value("hsl")
+                    .help("The color space in which the hue is rotated"),
+                Arg::with_name("output-format")
+                    .long("out")
+                    .short("o")
+                    .takes_value(true)
+                    .use_delimiter(true)
+                    .possible_values(super::COLOR_FORMATS)
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help(
+                        "Output format (html, hex, color space or code literal). Several \
+                        comma-separated formats can be specified, e.g. 'hex,oklch,rgb'",
+                    ),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+                Arg::with_name("size")
+                    .long("size")
+                    .short("s")
+                    .takes_value(true)
+                    .default_value("2")
+                    .help("Size of the color squares in terminal rows"),
+            ])
+            .args(&util::number_format_args())
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let mut colors = match matches.values_of("colors") {
+            Some(values) => util::values_to_colors(values, state)?,
+            None => vec![],
+        };
+
+        if !state.interactive && colors.is_empty() {
+            let input = stdin::read_all()?;
+            colors = color::parse(&input, state)?;
+        }
+
+        let scheme = matches.value_of("scheme").unwrap();
+        let scheme = match Scheme::from_name(scheme) {
+            Some(scheme) => scheme,
+            None => bail!("Unknown color scheme {:?}", scheme),
+        };
+        let spread = matches.value_of("spread").unwrap();
+        let spread = spread
+            .parse()
+            .with_context(|| format!("Invalid angle {:?}", spread))?;
+
+        let color_space = match matches.value_of("space").unwrap().to_lowercase().as_str() {
+            "lch" => ColorSpace::Lch,
+            "oklch" => ColorSpace::Oklch,
+            _ => ColorSpace::Hsl,
+        };
+
+        let mut output = util::get_color_formats(matches, "output-format")?;
+        if output.is_empty() {
+            let format = if colors.is_empty() {
+                None
+            } else if colors.windows(2).all(|c| c[0].1 == c[1].1) {
+                Some(colors[0].1).filter(|&c| c != ColorFormat::Html)
+            } else {
+                None
+            };
+            output.push(format.unwrap_or_default());
+        }
+        let output = output
+            .into_iter()
+            .map(|f| util::css_format(matches, f))
+            .collect();
+
+        Ok(Harmony {
+            colors,
+            scheme,
+            spread,
+            color_space,
+            output,
+            number_format: util::get_number_format(matches)?,
+            size: matches
+                .value_of("size")
+                .map(util::parse_size)
+                .unwrap_or(Ok(2))?,
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        for &(base, _) in &self.colors {
+            let colors = self.scheme.colors(base, self.spread, self.color_space);
+
+            if state.json {
+                Json::Object(vec![
+                    ("scheme", self.scheme.name().into()),
+                    ("base", base.into()),
+                    (
+                        "colors",
+                        Json::Array(colors.iter().map(|&c| c.into()).collect()),
+                    ),
+                ])
+                .print()?;
+                continue;
+            }
+            if state.color {
+                terminal::list_small(
+                    state,
+                    Some(self.scheme.name()),
+                    colors.iter().map(|&c| (c, self.output[0])),
+                    self.number_format,
+                    8,
+                )?;
+            }
+            terminal::show_colors(
+                state,
+                colors,
+                &self.output,
+                self.number_format,
+                None,
+                self.size,
+                false,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use clap::{App, ArgMatches, SubCommand};
 
 use super::{Cmd, APP_NAME, APP_VERSION, DEPENDENCIES};
-use crate::State;
+use crate::{terminal::json::Json, State};
 
 /// The `libs` subcommand
 pub struct Libs;
@@ -16,7 +16,15 @@ impl Cmd for Libs {
         Ok(Libs)
     }
 
-    fn run(&self, _state: State) -> Result<()> {
+    fn run(&self, state: State) -> Result<()> {
+        if state.json {
+            return Json::Object(vec![
+                ("name", APP_NAME.into()),
+                ("version", APP_VERSION.into()),
+                ("dependencies", DEPENDENCIES.into()),
+            ])
+            .print();
+        }
         println!("{} v{}\n{}", APP_NAME, APP_VERSION, DEPENDENCIES);
 
         Ok(())
@@ -0,0 +1,144 @@
+use anyhow::{bail, Result};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::path::{Path, PathBuf};
+
+use super::{grep, Cmd};
+use crate::{
+    color::{
+        contrast::{self, WcagLevel},
+        stylesheet, Algorithm,
+    },
+    terminal::{self, stdin, LintFailure},
+    State,
+};
+
+const FILES_HELP: &str = "\
+The CSS files to check. Directories are searched recursively for .css files, skipping hidden files. \
+If colo is used behind a pipe or outside of a terminal, the stylesheet can be provided via stdin, e.g.
+
+$ cat style.css | colo lint";
+
+/// The `lint` subcommand
+pub struct Lint {
+    files: Vec<PathBuf>,
+    stdin: Option<String>,
+    level: WcagLevel,
+}
+
+impl Cmd for Lint {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        SubCommand::with_name("lint")
+            .about("Check the text contrast in CSS files")
+            .long_about(
+                "Check the contrast of the text color and background color of each rule in \
+                CSS files. Rules that set only one of them are compared with the color \
+                inherited from ':root', 'html' or 'body', or with black text on a white \
+                background. Failing rules are printed with their location, the contrast \
+                and a suggested fix, and colo exits with a non-zero status.",
+            )
+            .args(&[
+                Arg::with_name("files")
+                    .takes_value(true)
+                    .index(1)
+                    .required(state.interactive)
+                    .multiple(true)
+                    .help(FILES_HELP),
+                Arg::with_name("level")
+                    .long("level")
+                    .takes_value(true)
+                    .possible_values(&["AA", "AAA", "AA-large", "AAA-large", "non-text"])
+                    .case_insensitive(true)
+                    .default_value("AA")
+                    .help("The WCAG 2 success criterion that must be met"),
+            ])
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let files: Vec<PathBuf> = matches
+            .values_of_os("files")
+            .map(|values| values.map(PathBuf::from).collect())
+            .unwrap_or_default();
+
+        let stdin = if !state.interactive && files.is_empty() {
+            Some(stdin::read_all()?)
+        } else {
+            None
+        };
+
+        let level = matches
+            .value_of("level")
+            .and_then(WcagLevel::from_name)
+            .unwrap_or(WcagLevel::Aa);
+
+        Ok(Lint {
+            files,
+            stdin,
+            level,
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        // only stylesheets are checked in directories
+        let is_css = |file: &Path| {
+            file.extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("css"))
+        };
+        let sources = grep::read_sources(self.stdin.as_deref(), &self.files, is_css)?;
+
+        let min = self.level.min_contrast();
+        let mut checked = 0;
+        let mut failed = 0;
+
+        for (path, text) in &sources {
+            let pairs = stylesheet::color_pairs(&stylesheet::parse_rules(text));
+            checked += pairs.len();
+
+            let failures: Vec<LintFailure> = pairs
+                .iter()
+                .filter_map(|pair| {
+                    let contrast = Algorithm::Wcag.contrast(pair.color, pair.background);
+                    if contrast >= min {
+                        return None;
+                    }
+                    // change the color that is set in the rule, preferably the text color
+                    let fix_background = pair.inherited_color;
+                    let fix = contrast::fix_contrast(
+                        Algorithm::Wcag,
+                        pair.color,
+                        pair.background,
+                        min,
+                        fix_background,
+                    )
+                    .map(|color| {
+                        let property = if fix_background {
+                            "background-color"
+                        } else {
+                            "color"
+                        };
+                        (property, color)
+                    });
+                    Some(LintFailure {
+                        path,
+                        pair,
+                        contrast,
+                        fix,
+                    })
+                })
+                .collect();
+
+            failed += failures.len();
+            terminal::print_lint_failures(state, &failures)?;
+        }
+
+        if failed > 0 {
+            bail!(
+                "{} of {} rules don't meet WCAG {} (minimum contrast {})",
+                failed,
+                checked,
+                self.level.name(),
+                min,
+            );
+        }
+        Ok(())
+    }
+}
@@ -1,23 +1,61 @@
 use anyhow::Result;
-use clap::{App, ArgMatches, SubCommand};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::{path::PathBuf, slice};
 
-use crate::terminal;
+use crate::{
+    color::{format::NumberFormat, ColorFormat},
+    export::{self, Sheet, Swatch},
+    terminal,
+};
 
-use super::Cmd;
+use super::{util, Cmd};
 
 /// The `list` subcommand
-pub struct List;
+pub struct List {
+    output: Option<ColorFormat>,
+    export: Option<PathBuf>,
+}
 
 impl Cmd for List {
     fn command<'a, 'b>(_state: crate::State) -> App<'a, 'b> {
-        SubCommand::with_name("list").about("Lists all HTML colors")
+        SubCommand::with_name("list")
+            .about("Lists all HTML colors and user-defined colors")
+            .arg(
+                Arg::with_name("output-format")
+                    .long("out")
+                    .short("o")
+                    .takes_value(true)
+                    .possible_values(super::COLOR_FORMATS)
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help(
+                        "Print the colors in this format, e.g. 'hex' or 'latex' \
+                        (see `colo show --help` for possible values)",
+                    ),
+            )
+            .arg(util::export_arg())
     }
 
-    fn parse(_matches: &ArgMatches, _state: &mut crate::State) -> Result<Self> {
-        Ok(List)
+    fn parse(matches: &ArgMatches, _state: &mut crate::State) -> Result<Self> {
+        Ok(List {
+            output: util::get_color_format(matches, "output-format")?,
+            export: util::get_export_path(matches),
+        })
     }
 
     fn run(&self, state: crate::State) -> Result<()> {
-        terminal::list(state)
+        if let Some(path) = &self.export {
+            let swatches: Vec<Swatch> = terminal::list_colors()
+                .map(|(name, color)| Swatch::with_name(name, color))
+                .collect();
+            let sheet = Sheet {
+                swatches: &swatches,
+                formats: slice::from_ref(self.output.as_ref().unwrap_or(&ColorFormat::Hex)),
+                number_format: NumberFormat::default(),
+                image: Default::default(),
+            };
+            return export::export(path, &sheet);
+        }
+        terminal::list(state, self.output)
     }
 }
@@ -1,13 +1,14 @@
-use std::{fmt::Write, iter};
+use std::{fmt::Write, iter, path::PathBuf, slice};
 
 use anyhow::{anyhow, bail, Result};
 use clap::{Arg, ArgMatches, SubCommand};
-use color::ColorFormat;
+use color::{format::NumberFormat, template::Template, ColorFormat};
 
 use super::{util, Cmd};
 use crate::{
     color::{self, Color, ColorSpace},
-    terminal::{self, stdin},
+    export::{self, Sheet, Swatch},
+    terminal::{self, json::Json, stdin},
     State,
 };
 
@@ -16,7 +17,10 @@ pub struct Mix {
     colors: Vec<(Color, ColorFormat, f64)>,
     color_space: ColorSpace,
     output: ColorFormat,
+    template: Option<Template>,
+    number_format: NumberFormat,
     size: u32,
+    export: Option<PathBuf>,
 }
 
 const COLOR_HELP_MESSAGE: &str = "\
@@ -74,6 +78,7 @@ impl Cmd for Mix {
                         "hunterlab",
                         "xyz",
                         "yxy",
+                        "oklab",
                     ])
                     .case_insensitive(true)
                     .default_value("lab"),
@@ -91,10 +96,23 @@ impl Cmd for Mix {
                     .hide_possible_values(true)
                     .case_insensitive(true)
                     .help(
-                        "Output format (html, hex, or color space) [possible values: rgb, cmy, \
-                        cmyk, hsv, hsl, lch, luv, lab, hunterlab, xyz, yxy, gry, hex, html]",
+                        "Output format (html, hex, color space or code literal) \
+                        [possible values: rgb, cmy, cmyk, hsv, hsl, lch, luv, lab, hunterlab, \
+                        xyz, yxy, oklab, oklch, gry, hex, html, nearest, rust, c, swift, kotlin, \
+                        flutter, qt, latex, latex-rgb, p3]",
                     ),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+                Arg::with_name("format")
+                    .long("format")
+                    .short("f")
+                    .takes_value(true)
+                    .help(super::TEMPLATE_HELP),
             ])
+            .arg(util::export_arg())
+            .args(&util::number_format_args())
     }
 
     fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
@@ -143,12 +161,18 @@ impl Cmd for Mix {
                 }
             })
             .unwrap_or_default();
+        let output = util::css_format(matches, output);
+        let template = util::get_template(matches, "format")?;
+        let number_format = util::get_number_format(matches)?;
 
         Ok(Mix {
             colors,
             color_space,
             output,
+            template,
+            number_format,
             size,
+            export: util::get_export_path(matches),
         })
     }
 
@@ -169,11 +193,42 @@ impl Cmd for Mix {
             )?;
         let color = Color::new_unchecked(self.color_space, &components);
 
+        if state.json {
+            let colors = self
+                .colors
+                .iter()
+                .map(|&(c, _, w)| Json::Object(vec![("color", c.into()), ("weight", w.into())]))
+                .collect();
+            return Json::Object(vec![
+                ("colors", Json::Array(colors)),
+                ("color_space", self.color_space.name().into()),
+                ("result", color.into()),
+            ])
+            .print();
+        }
+
+        if let Some(path) = &self.export {
+            let swatches: Vec<Swatch> = self
+                .colors
+                .iter()
+                .map(|&(c, ..)| Swatch::new(c))
+                .chain(iter::once(Swatch::with_name("Result", color)))
+                .collect();
+            let sheet = Sheet {
+                swatches: &swatches,
+                formats: slice::from_ref(&self.output),
+                number_format: self.number_format,
+                image: Default::default(),
+            };
+            return export::export(path, &sheet);
+        }
+
         if state.color {
             terminal::list_small(
                 state,
                 Some("Colors"),
                 self.colors.iter().map(|&(a, b, _)| (a, b)),
+                self.number_format,
                 6,
             )?;
 
@@ -192,7 +247,15 @@ impl Cmd for Mix {
             println!("Weights: {}", weights);
         }
 
-        terminal::show_colors(state, iter::once(color), self.output, self.size)
+        terminal::show_colors(
+            state,
+            iter::once(color),
+            slice::from_ref(&self.output),
+            self.number_format,
+            self.template.as_ref(),
+            self.size,
+            false,
+        )
     }
 }
 
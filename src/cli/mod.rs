@@ -1,26 +1,43 @@
 use anyhow::{bail, Result};
 use clap::{App, AppSettings, Arg, ArgMatches};
+use std::{ffi::OsString, path::Path};
 
-use crate::State;
+use crate::{color, State};
 
+mod adjust;
 mod contrast;
+mod cvd;
+mod diff;
 mod gradient;
+mod grep;
+mod harmony;
 mod libs;
+mod lint;
 mod list;
 mod mix;
+mod palette;
 mod pick;
 mod printf;
+mod scale;
 mod show;
 mod term;
 mod textcolor;
 
+pub(crate) use adjust::Adjust;
 pub(crate) use contrast::Contrast;
+pub(crate) use cvd::Cvd;
+pub(crate) use diff::Diff;
 pub(crate) use gradient::Gradient;
+pub(crate) use grep::Grep;
+pub(crate) use harmony::Harmony;
 pub(crate) use libs::Libs;
+pub(crate) use lint::Lint;
 pub(crate) use list::List;
 pub(crate) use mix::Mix;
+pub(crate) use palette::Palette;
 pub(crate) use pick::Pick;
 pub(crate) use printf::Printf;
+pub(crate) use scale::Scale;
 pub(crate) use show::Show;
 pub(crate) use term::Term;
 pub(crate) use textcolor::TextColor;
@@ -49,11 +66,41 @@ const COLOR_FORMATS: &[&str] = &[
     "hunterlab",
     "xyz",
     "yxy",
+    "oklab",
+    "oklch",
     "html",
+    "nearest",
     "hex",
     "gry",
+    "rust",
+    "c",
+    "swift",
+    "kotlin",
+    "flutter",
+    "qt",
+    "latex",
+    "latex-rgb",
+    "p3",
 ];
 
+/// Aliases of subcommands whose prefix is shared by other subcommands. They
+/// have to be expanded before parsing, because clap prefers an ambiguous
+/// prefix match over an alias.
+const SUBCOMMAND_ALIASES: &[(&str, &str)] = &[("c", "contrast"), ("g", "gradient"), ("s", "show")];
+
+/// Help message of the `--format` argument
+const TEMPLATE_HELP: &str = "\
+Output template, e.g. '{r} {g} {b}' or '{h:.0}deg {s:%}'. Placeholders:
+
+* Color components: r, g, b, h, s, l, v, c, m, y, k, or qualified with the
+  color space, e.g. {hsv.s}, {lab.a}
+* Color spaces, e.g. {hsl}, or {hex}, {html}, {nearest}
+
+Components can be scaled ({r/255}, {l*100}) and accept a precision ({h:.2}).
+'%' multiplies the value with 100 and appends a percent sign ({s:.1%}).
+{hex:upper} prints uppercase hex digits. Use {{ and }} for literal braces.
+Overrides --out";
+
 /// A clap (sub)command.
 ///
 /// Each subcommand is defined in its own module and is registered in
@@ -81,7 +128,8 @@ pub(crate) struct MainCmd {
 impl MainCmd {
     /// Parses the CLI arguments and executes the appropriate function.
     pub(crate) fn main(state: &mut State) -> Result<()> {
-        let matches = Self::command(*state).get_matches();
+        let args = expand_aliases(std::env::args_os());
+        let matches = Self::command(*state).get_matches_from(args);
         let s = Self::parse(&matches, state)?;
         s.run(*state)
     }
@@ -108,9 +156,17 @@ impl Cmd for MainCmd {
             .subcommand(Pick::command(state))
             .subcommand(Term::command(state))
             .subcommand(Contrast::command(state))
+            .subcommand(Diff::command(state))
+            .subcommand(Cvd::command(state))
+            .subcommand(Palette::command(state))
             .subcommand(TextColor::command(state))
+            .subcommand(Adjust::command(state))
+            .subcommand(Harmony::command(state))
+            .subcommand(Scale::command(state))
             .subcommand(Mix::command(state))
             .subcommand(Gradient::command(state))
+            .subcommand(Grep::command(state))
+            .subcommand(Lint::command(state))
             .subcommand(List::command(state))
             .subcommand(Libs::command(state))
             .arg(
@@ -125,6 +181,20 @@ impl Cmd for MainCmd {
                         when used behind a pipe or not in a tty",
                     ),
             )
+            .arg(Arg::with_name("json").long("json").global(true).help(
+                "Print the result as JSON. Commands that print multiple results \
+                        print one JSON object per line",
+            ))
+            .arg(
+                Arg::with_name("config")
+                    .long("config")
+                    .takes_value(true)
+                    .env("COLO_CONFIG")
+                    .help(
+                        "Path of the config file with user-defined colors and palettes. \
+                        Defaults to colo/colors.conf in the user's config directory",
+                    ),
+            )
             .max_term_width(100)
     }
 
@@ -140,6 +210,13 @@ impl Cmd for MainCmd {
             }
             _ => {}
         }
+        if matches.is_present("json") {
+            colored::control::set_override(false);
+            state.color = false;
+            state.json = true;
+        }
+
+        color::load_aliases(matches.value_of_os("config").map(Path::new), *state)?;
 
         let subcommand: Box<dyn Cmd> = match matches.subcommand() {
             ("show", Some(matches)) => Box::new(Show::parse(matches, state)?),
@@ -149,9 +226,17 @@ impl Cmd for MainCmd {
             ("pick", Some(matches)) => Box::new(Pick::parse(matches, state)?),
             ("list", Some(matches)) => Box::new(List::parse(matches, state)?),
             ("contrast", Some(matches)) => Box::new(Contrast::parse(matches, state)?),
+            ("diff", Some(matches)) => Box::new(Diff::parse(matches, state)?),
+            ("cvd", Some(matches)) => Box::new(Cvd::parse(matches, state)?),
+            ("palette", Some(matches)) => Box::new(Palette::parse(matches, state)?),
             ("textcolor", Some(matches)) => Box::new(TextColor::parse(matches, state)?),
+            ("adjust", Some(matches)) => Box::new(Adjust::parse(matches, state)?),
+            ("harmony", Some(matches)) => Box::new(Harmony::parse(matches, state)?),
+            ("scale", Some(matches)) => Box::new(Scale::parse(matches, state)?),
             ("mix", Some(matches)) => Box::new(Mix::parse(matches, state)?),
             ("gradient", Some(matches)) => Box::new(Gradient::parse(matches, state)?),
+            ("grep", Some(matches)) => Box::new(Grep::parse(matches, state)?),
+            ("lint", Some(matches)) => Box::new(Lint::parse(matches, state)?),
             (c, _) => bail!("Unknown subcommand {:?}", c),
         };
 
@@ -162,3 +247,59 @@ impl Cmd for MainCmd {
         self.subcommand.run(state)
     }
 }
+
+/// Replaces the subcommand with its full name if it is one of the
+/// `SUBCOMMAND_ALIASES`
+fn expand_aliases(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    let mut is_value = false;
+    for arg in args.iter_mut().skip(1) {
+        match arg.to_str() {
+            _ if is_value => is_value = false,
+            Some("--color") | Some("--config") => is_value = true,
+            Some(a) if a.starts_with('-') => {}
+            Some(a) => {
+                if let Some(&(_, name)) = SUBCOMMAND_ALIASES.iter().find(|&&(alias, _)| alias == a)
+                {
+                    *arg = name.into();
+                }
+                break;
+            }
+            None => break,
+        }
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_aliases, Cmd, MainCmd};
+    use crate::State;
+
+    fn subcommand(args: &[&str]) -> String {
+        let state = State {
+            interactive: false,
+            color: false,
+            json: false,
+        };
+        let args = expand_aliases(args.iter().map(Into::into));
+        let matches = MainCmd::command(state).get_matches_from_safe(args).unwrap();
+        matches.subcommand_name().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_subcommand_aliases() {
+        assert_eq!(subcommand(&["colo", "c", "red", "blue"]), "contrast");
+        assert_eq!(subcommand(&["colo", "cv", "red"]), "cvd");
+        assert_eq!(subcommand(&["colo", "g", "red", "blue"]), "gradient");
+        assert_eq!(
+            subcommand(&["colo", "--color", "never", "g", "red"]),
+            "gradient"
+        );
+        assert_eq!(subcommand(&["colo", "gre", "red"]), "grep");
+        assert_eq!(subcommand(&["colo", "s", "orange"]), "show");
+        assert_eq!(subcommand(&["colo", "--json", "s", "orange"]), "show");
+        assert_eq!(subcommand(&["colo", "sc", "orange"]), "scale");
+        assert_eq!(subcommand(&["colo", "--config", "g", "gre", "red"]), "grep");
+    }
+}
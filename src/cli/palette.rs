@@ -0,0 +1,140 @@
+use anyhow::{bail, Result};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use std::iter;
+
+use super::{util, Cmd};
+use crate::{
+    color::{cvd::Simulation, difference::DeltaE, Color, ColorFormat},
+    terminal::{self, stdin, PaletteCheck},
+    State,
+};
+
+const COLOR_HELP: &str = "\
+The colors of the palette, e.g. 'red green blue' or a palette such as '@brand'. Supported formats:
+
+* HTML color name, e.g. 'rebeccapurple'
+* Hexadecimal RGB color, e.g. '07F', '0077FF'
+* Color components, e.g. 'hsl(30, 100%, 50%)'
+  Commas and parentheses are optional.
+  For supported color spaces, see <https://aloso.github.io/colo/color_spaces>
+
+If colo is used behind a pipe or outside of a terminal, the colors can be provided via stdin, e.g.
+
+$ echo red green blue | colo palette check";
+
+/// The `palette` subcommand
+pub struct Palette {
+    pub colors: Vec<(Color, ColorFormat)>,
+    pub metric: DeltaE,
+    pub min: f64,
+    pub simulations: Vec<Simulation>,
+}
+
+impl Cmd for Palette {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        SubCommand::with_name("palette")
+            .about("Analyze color palettes")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("check")
+                    .about("Check that the colors of a palette can be told apart")
+                    .long_about(
+                        "Compute the smallest perceptual difference (ΔE) between any two \
+                        colors of a palette, with normal vision and with simulated color \
+                        vision deficiencies. The closest pair is shown for each of them. \
+                        If a difference is below the minimum, colo exits with a non-zero status.",
+                    )
+                    .args(&[
+                        Arg::with_name("colors")
+                            .help(COLOR_HELP)
+                            .index(1)
+                            .multiple(true)
+                            .required(state.interactive),
+                        util::metric_arg(),
+                        Arg::with_name("min")
+                            .long("min")
+                            .takes_value(true)
+                            .value_name("difference")
+                            .help(
+                                "The smallest accepted difference (default: 2.3 for cie76, \
+                                0.02 for ok, otherwise 1). Series in charts should differ \
+                                more, e.g. 10 with ciede2000",
+                            ),
+                    ])
+                    .args(&util::simulation_args()),
+            )
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let matches = match matches.subcommand() {
+            ("check", Some(matches)) => matches,
+            (c, _) => bail!("Unknown subcommand {:?}", c),
+        };
+
+        let mut colors = match matches.values_of("colors") {
+            Some(values) => util::values_to_colors(values, state)?,
+            None => vec![],
+        };
+
+        if !state.interactive && colors.is_empty() {
+            let text = stdin::read_all()?;
+            colors = util::values_to_colors(iter::once(text.as_str()), state)?;
+        }
+        if colors.len() < 2 {
+            bail!("Expected at least 2 colors, got {}", colors.len());
+        }
+
+        let metric = util::get_metric(matches)?;
+        let min = match matches.value_of("min") {
+            Some(min) => match min.parse::<f64>() {
+                Ok(min) if min >= 0.0 => min,
+                _ => bail!("Invalid minimum difference {:?}", min),
+            },
+            None => metric.just_noticeable(),
+        };
+
+        Ok(Palette {
+            colors,
+            metric,
+            min,
+            simulations: util::get_simulations(matches)?,
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        let check = |vision: String, simulate: &dyn Fn(Color) -> Color| {
+            let perceived: Vec<Color> = self.colors.iter().map(|&(c, _)| simulate(c)).collect();
+            let closest = self.metric.closest_pair(&perceived).unwrap();
+            PaletteCheck {
+                vision,
+                perceived,
+                closest,
+            }
+        };
+
+        let checks: Vec<PaletteCheck> = iter::once(check("normal vision".into(), &|c| c))
+            .chain(
+                self.simulations
+                    .iter()
+                    .map(|s| check(s.to_string(), &|c| s.simulate(c))),
+            )
+            .collect();
+
+        terminal::print_palette_checks(state, &self.colors, &checks, self.metric, self.min)?;
+
+        let failed: Vec<&str> = checks
+            .iter()
+            .filter(|check| check.closest.2 < self.min)
+            .map(|check| check.vision.as_str())
+            .collect();
+        if !failed.is_empty() {
+            bail!(
+                "Some colors are hard to distinguish with {} (minimum {} {})",
+                failed.join(", "),
+                self.metric.name(),
+                self.min,
+            );
+        }
+        Ok(())
+    }
+}
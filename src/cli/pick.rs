@@ -31,7 +31,16 @@ impl Cmd for Pick {
                     .takes_value(true)
                     .possible_values(super::COLOR_FORMATS)
                     .case_insensitive(true)
-                    .help("Output format (html, hex, or color space)"),
+                    .help("Output format (html, hex, color space or code literal)"),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+                Arg::with_name("format")
+                    .long("format")
+                    .short("f")
+                    .takes_value(true)
+                    .help(super::TEMPLATE_HELP),
                 Arg::with_name("size")
                     .long("size")
                     .short("s")
@@ -52,6 +61,7 @@ impl Cmd for Pick {
                     .use_delimiter(false)
                     .help(COLOR_HELP_MESSAGE),
             ])
+            .args(&util::number_format_args())
     }
 
     fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
@@ -60,6 +70,9 @@ impl Cmd for Pick {
             .map(util::parse_size)
             .unwrap_or(Ok(4))?;
         let output = util::get_color_format(matches, "output-format")?.unwrap_or_default();
+        let output = util::css_format(matches, output);
+        let template = util::get_template(matches, "format")?;
+        let number_format = util::get_number_format(matches)?;
 
         let (color, cs) = get_color_options(matches, state)?;
         let cs = get_color_space_option(matches).or(cs);
@@ -68,8 +81,14 @@ impl Cmd for Pick {
 
         let show = Show {
             colors: vec![(color, color.get_color_format())],
-            output,
+            output: vec![output],
+            number_format,
+            template,
             size,
+            all: false,
+            export: None,
+            image: Default::default(),
+            original: None,
         };
         Ok(Pick(show))
     }
@@ -92,10 +111,10 @@ fn get_color_options(
                 bail!("Only one color can be specified, found {}", colors.len());
             }
 
-            Ok(colors.get(0).map(|&(c, f)| {
+            Ok(colors.first().map(|&(c, f)| {
                 let cs = match f {
                     ColorFormat::Normal(cs) => cs,
-                    ColorFormat::Hex | ColorFormat::Html => ColorSpace::Rgb,
+                    _ => ColorSpace::Rgb,
                 };
                 (c, cs)
             }))
@@ -75,7 +75,7 @@ impl Cmd for Printf {
         Ok(Printf { text, colors })
     }
 
-    fn run(&self, _state: State) -> Result<()> {
-        terminal::print_text(&self.text, &self.colors)
+    fn run(&self, state: State) -> Result<()> {
+        terminal::print_text(state, &self.text, &self.colors)
     }
 }
@@ -0,0 +1,202 @@
+use anyhow::{bail, Result};
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use super::{util, Cmd};
+use crate::{
+    color::{
+        self,
+        format::NumberFormat,
+        scale::{self, TAILWIND_NAMES},
+        Color, ColorFormat, ColorSpace,
+    },
+    terminal::{self, stdin},
+    State,
+};
+
+const COLOR_HELP_MESSAGE: &str = "\
+The seed colors. Multiple colors can be specified. Supported formats:
+
+* HTML color name, e.g. 'rebeccapurple'
+* Hexadecimal RGB color, e.g. '07F', '0077FF'
+* Color components, e.g. 'hsl(30, 100%, 50%)'
+  Commas and parentheses are optional.
+  For supported color spaces, see <https://aloso.github.io/colo/color_spaces>
+
+If colo is used behind a pipe or outside of a terminal, the colors can be provided via stdin, e.g.
+
+$ echo '#3b82f6' | colo scale";
+
+const NAMES_HELP: &str = "\
+The names of the steps:
+
+* tailwind: 50, 100, 200, ..., 900, 950 (requires 11 steps)
+* hundreds: 100, 200, 300, ...
+* numbers: 1, 2, 3, ...
+* a comma-separated list of names, e.g. 'lightest,light,base,dark,darkest'
+
+The default is 'tailwind' with 11 steps, otherwise 'hundreds'.";
+
+/// The `scale` subcommand
+pub struct Scale {
+    pub colors: Vec<(Color, ColorFormat)>,
+    pub names: Vec<String>,
+    pub lightest: f64,
+    pub darkest: f64,
+    pub color_space: ColorSpace,
+    pub output: ColorFormat,
+    pub number_format: NumberFormat,
+}
+
+impl Cmd for Scale {
+    fn command<'a, 'b>(state: State) -> App<'a, 'b> {
+        SubCommand::with_name("scale")
+            .about("Create a color scale from a seed color")
+            .long_about(
+                "Create a numbered scale from light to dark with the hue of a seed color, \
+            e.g. for the colors of a design system. The lightness of the steps is \
+            perceptually even, and the chroma is reduced towards the lightest and \
+            darkest steps. The steps are printed as 'name: value' pairs.",
+            )
+            .args(&[
+                Arg::with_name("colors")
+                    .takes_value(true)
+                    .required(state.interactive)
+                    .help(COLOR_HELP_MESSAGE)
+                    .multiple(true)
+                    .use_delimiter(false),
+                Arg::with_name("steps")
+                    .long("steps")
+                    .short("n")
+                    .takes_value(true)
+                    .help("The number of steps (default: 11, or the number of names)"),
+                Arg::with_name("lightness")
+                    .long("lightness")
+                    .short("l")
+                    .takes_value(true)
+                    .number_of_values(2)
+                    .require_delimiter(true)
+                    .value_names(&["lightest", "darkest"])
+                    .help(
+                        "The lightness of the lightest and darkest step, in percent of \
+                        the lightness range, e.g. '97,25' (the default)",
+                    ),
+                Arg::with_name("names")
+                    .long("names")
+                    .takes_value(true)
+                    .help(NAMES_HELP),
+                Arg::with_name("space")
+                    .long("space")
+                    .takes_value(true)
+                    .possible_values(&["lab", "lch", "oklch"])
+                    .case_insensitive(true)
+                    .default_value("oklch")
+                    .help("The color space in which the lightness and chroma are computed"),
+                Arg::with_name("output-format")
+                    .long("out")
+                    .short("o")
+                    .takes_value(true)
+                    .possible_values(super::COLOR_FORMATS)
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help("Output format (html, hex, color space or code literal)"),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+            ])
+            .args(&util::number_format_args())
+    }
+
+    fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
+        let mut colors = match matches.values_of("colors") {
+            Some(values) => util::values_to_colors(values, state)?,
+            None => vec![],
+        };
+
+        if !state.interactive && colors.is_empty() {
+            let input = stdin::read_all()?;
+            colors = color::parse(&input, state)?;
+        }
+
+        let steps = match matches.value_of("steps") {
+            Some(steps) => match steps.parse::<usize>() {
+                Ok(steps) if steps >= 2 => Some(steps),
+                _ => bail!("Invalid number of steps {:?}, expected at least 2", steps),
+            },
+            None => None,
+        };
+        let names = match (matches.value_of("names"), steps) {
+            (Some(list), _) if list.contains(',') => {
+                let names: Vec<String> = list.split(',').map(|n| n.trim().to_string()).collect();
+                if steps.is_some_and(|steps| steps != names.len()) {
+                    bail!("Expected {} names, got {}", steps.unwrap(), names.len());
+                }
+                names
+            }
+            (Some("tailwind"), Some(steps)) if steps != TAILWIND_NAMES.len() => {
+                bail!("The 'tailwind' names require 11 steps")
+            }
+            (Some("tailwind"), _) | (None, None | Some(11)) => {
+                TAILWIND_NAMES.iter().map(|&n| n.to_string()).collect()
+            }
+            (Some("hundreds") | None, steps) => (1..=steps.unwrap_or(11))
+                .map(|i| (i * 100).to_string())
+                .collect(),
+            (Some("numbers"), steps) => (1..=steps.unwrap_or(11)).map(|i| i.to_string()).collect(),
+            (Some(names), _) => bail!(
+                "Invalid names {:?}, expected 'tailwind', 'hundreds', 'numbers' \
+                or a comma-separated list",
+                names
+            ),
+        };
+
+        let (lightest, darkest) = match matches.values_of("lightness") {
+            Some(mut values) => {
+                let mut parse = || -> Result<f64> {
+                    let value = values.next().unwrap();
+                    match value.trim().trim_end_matches('%').parse::<f64>() {
+                        Ok(l) if (0.0..=100.0).contains(&l) => Ok(l),
+                        _ => bail!("Invalid lightness {:?}, expected 0 to 100", value),
+                    }
+                };
+                (parse()?, parse()?)
+            }
+            None => (97.0, 25.0),
+        };
+
+        let color_space = match matches.value_of("space").unwrap().to_lowercase().as_str() {
+            "oklch" => ColorSpace::Oklch,
+            _ => ColorSpace::Lch,
+        };
+
+        let output = util::get_color_format(matches, "output-format")?.unwrap_or_default();
+
+        Ok(Scale {
+            colors,
+            names,
+            lightest,
+            darkest,
+            color_space,
+            output: util::css_format(matches, output),
+            number_format: util::get_number_format(matches)?,
+        })
+    }
+
+    fn run(&self, state: State) -> Result<()> {
+        for (i, &(seed, _)) in self.colors.iter().enumerate() {
+            if i > 0 && !state.json {
+                println!();
+            }
+            let colors = scale::scale(
+                seed,
+                self.names.len(),
+                self.lightest,
+                self.darkest,
+                self.color_space,
+            );
+            let steps: Vec<(String, Color)> = self.names.iter().cloned().zip(colors).collect();
+            terminal::print_scale(state, &steps, self.output, self.number_format)?;
+        }
+        Ok(())
+    }
+}
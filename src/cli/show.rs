@@ -1,9 +1,11 @@
 use anyhow::Result;
 use clap::{App, Arg, ArgMatches, SubCommand};
+use std::path::PathBuf;
 
 use super::{util, Cmd};
 use crate::{
-    color::{self, Color, ColorFormat},
+    color::{self, cvd::Simulation, format::NumberFormat, template::Template, Color, ColorFormat},
+    export::{self, ImageOptions, Sheet, Swatch},
     terminal::{self, stdin},
     State,
 };
@@ -24,8 +26,15 @@ $ echo orange blue FF7700 | colo show";
 /// The `show`/`s` subcommand
 pub struct Show {
     pub colors: Vec<(Color, ColorFormat)>,
-    pub output: ColorFormat,
+    pub output: Vec<ColorFormat>,
+    pub template: Option<Template>,
+    pub number_format: NumberFormat,
     pub size: u32,
+    pub all: bool,
+    pub export: Option<PathBuf>,
+    pub image: ImageOptions,
+    /// The colors before daltonization
+    pub original: Option<(Simulation, Vec<Color>)>,
 }
 
 impl Cmd for Show {
@@ -44,13 +53,30 @@ impl Cmd for Show {
                     .long("out")
                     .short("o")
                     .takes_value(true)
+                    .use_delimiter(true)
                     .possible_values(super::COLOR_FORMATS)
                     .hide_possible_values(true)
                     .case_insensitive(true)
                     .help(
-                        "Output format (html, hex, or color space) [possible values: rgb, cmy, \
-                        cmyk, hsv, hsl, lch, luv, lab, hunterlab, xyz, yxy, gry, hex, html]",
+                        "Output format (html, hex, color space or code literal). Several \
+                        comma-separated formats can be specified, e.g. 'hex,oklch,rgb' \
+                        [possible values: rgb, cmy, cmyk, hsv, hsl, lch, luv, lab, hunterlab, \
+                        xyz, yxy, oklab, oklch, gry, hex, html, nearest, rust, c, swift, kotlin, \
+                        flutter, qt, latex, latex-rgb, p3]",
                     ),
+                Arg::with_name("css").long("css").help(
+                    "Print colors as valid CSS. Formats that aren't supported by CSS are \
+                        converted to the closest CSS color function",
+                ),
+                Arg::with_name("all")
+                    .long("all")
+                    .short("a")
+                    .help("Print the colors in all supported color spaces"),
+                Arg::with_name("format")
+                    .long("format")
+                    .short("f")
+                    .takes_value(true)
+                    .help(super::TEMPLATE_HELP),
                 Arg::with_name("size")
                     .long("size")
                     .short("s")
@@ -58,6 +84,10 @@ impl Cmd for Show {
                     .default_value("4")
                     .help("Size of the color square in terminal rows"),
             ])
+            .arg(util::daltonize_arg())
+            .arg(util::export_arg())
+            .args(&util::image_size_args())
+            .args(&util::number_format_args())
     }
 
     fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
@@ -76,31 +106,71 @@ impl Cmd for Show {
             colors = color::parse(&input, state)?;
         }
 
-        let output = util::get_color_format(matches, "output-format")?
-            .or_else(|| {
-                if colors.is_empty() {
-                    None
-                } else if colors.windows(2).all(|c| c[0].1 == c[1].1) {
-                    Some(colors[0].1).filter(|&c| c != ColorFormat::Html)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_default();
+        let mut output = util::get_color_formats(matches, "output-format")?;
+        if output.is_empty() {
+            let format = if colors.is_empty() {
+                None
+            } else if colors.windows(2).all(|c| c[0].1 == c[1].1) {
+                Some(colors[0].1).filter(|&c| c != ColorFormat::Html)
+            } else {
+                None
+            };
+            output.push(format.unwrap_or_default());
+        }
+        let output = output
+            .into_iter()
+            .map(|f| util::css_format(matches, f))
+            .collect();
+        let template = util::get_template(matches, "format")?;
+
+        let original = util::get_daltonize(matches).map(|simulation| {
+            let original = colors.iter().map(|&(c, _)| c).collect();
+            for (color, _) in &mut colors {
+                *color = simulation.daltonize(*color);
+            }
+            (simulation, original)
+        });
+        let number_format = util::get_number_format(matches)?;
 
         Ok(Show {
             colors,
             output,
+            template,
+            number_format,
             size,
+            all: matches.is_present("all"),
+            export: util::get_export_path(matches),
+            image: util::get_image_options(matches)?,
+            original,
         })
     }
 
     fn run(&self, state: State) -> Result<()> {
+        if let Some(path) = &self.export {
+            let swatches: Vec<Swatch> = self.colors.iter().map(|&(c, _)| Swatch::new(c)).collect();
+            let sheet = Sheet {
+                swatches: &swatches,
+                formats: &self.output,
+                number_format: self.number_format,
+                image: self.image,
+            };
+            return export::export(path, &sheet);
+        }
+
         terminal::show_colors(
             state,
             self.colors.iter().map(|&(c, _)| c),
-            self.output,
+            &self.output,
+            self.number_format,
+            self.template.as_ref(),
             self.size,
-        )
+            self.all,
+        )?;
+
+        if let Some((simulation, original)) = &self.original {
+            let colors: Vec<Color> = self.colors.iter().map(|&(c, _)| c).collect();
+            terminal::daltonize_report(state, *simulation, original, &colors)?;
+        }
+        Ok(())
     }
 }
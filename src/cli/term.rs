@@ -17,7 +17,7 @@ impl Cmd for Term {
         Ok(Term)
     }
 
-    fn run(&self, _state: crate::State) -> Result<()> {
-        terminal::term_colors()
+    fn run(&self, state: crate::State) -> Result<()> {
+        terminal::term_colors(state)
     }
 }
@@ -1,10 +1,13 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{App, Arg, ArgMatches, SubCommand};
 use std::iter;
 
 use super::{util, Cmd};
 use crate::{
-    color::{Color, ColorFormat},
+    color::{
+        contrast::{self, WcagLevel},
+        Algorithm, Color, ColorFormat,
+    },
     terminal::{self, stdin},
     State,
 };
@@ -22,9 +25,29 @@ If colo is used behind a pipe or outside of a terminal, the colors can be provid
 
 $ echo orange blue FF7700 | colo textcolor";
 
+const CANDIDATES_HELP: &str = "\
+The text colors to choose from, separated by spaces, e.g. '#222 #fff navy' or a palette such as \
+'@brand'. For each background color, the candidate with the highest contrast is returned.";
+
+const TINT_HELP: &str = "\
+Generate a text color with the hue and chroma of the background that just reaches the target \
+contrast. The target is a contrast ratio or a WCAG level such as 'AA' or 'AAA-large'.";
+
+/// How the text color is chosen
+pub enum Mode {
+    /// Black or white
+    BlackOrWhite,
+    /// The candidate with the highest contrast
+    Candidates(Vec<(Color, ColorFormat)>),
+    /// A color in the hue of the background with the target contrast
+    Tint(f64),
+}
+
 /// The `textcolor` subcommand
 pub struct TextColor {
     pub colors: Vec<(Color, ColorFormat)>,
+    pub mode: Mode,
+    pub output: Option<ColorFormat>,
 }
 
 impl Cmd for TextColor {
@@ -35,15 +58,40 @@ impl Cmd for TextColor {
             .long_about(
                 "Return a readable text color (black or white) for each given background color. \
             This can also be used in the opposite way, i.e. to create a background color \
-            for a given text color.",
+            for a given text color. With --candidates or --tint, the text color is chosen \
+            from a list of colors or derived from the background color instead.",
             )
-            .arg(
+            .args(&[
                 Arg::with_name("colors")
                     .help(COLOR_HELP_MESSAGE)
                     .index(1)
                     .multiple(true)
                     .required(state.interactive),
-            )
+                Arg::with_name("candidates")
+                    .long("candidates")
+                    .short("c")
+                    .takes_value(true)
+                    .value_name("colors")
+                    .help(CANDIDATES_HELP),
+                Arg::with_name("tint")
+                    .long("tint")
+                    .short("t")
+                    .takes_value(true)
+                    .value_name("target")
+                    .conflicts_with("candidates")
+                    .help(TINT_HELP),
+                Arg::with_name("output-format")
+                    .long("out")
+                    .short("o")
+                    .takes_value(true)
+                    .possible_values(super::COLOR_FORMATS)
+                    .hide_possible_values(true)
+                    .case_insensitive(true)
+                    .help(
+                        "Output format of the text colors with --candidates or --tint \
+                        (html, hex, color space or code literal)",
+                    ),
+            ])
     }
 
     fn parse(matches: &ArgMatches, &mut state: &mut State) -> Result<Self> {
@@ -57,10 +105,69 @@ impl Cmd for TextColor {
             colors = util::values_to_colors(iter::once(text.as_str()), state)?;
         }
 
-        Ok(TextColor { colors })
+        let mode = if let Some(candidates) = matches.value_of("candidates") {
+            let candidates = util::values_to_colors(iter::once(candidates), state)?;
+            if candidates.is_empty() {
+                bail!("No candidate colors were specified");
+            }
+            Mode::Candidates(candidates)
+        } else if let Some(target) = matches.value_of("tint") {
+            match (target.parse::<f64>(), WcagLevel::from_name(target)) {
+                (Ok(target), _) if (1.0..=21.0).contains(&target) => Mode::Tint(target),
+                (_, Some(level)) => Mode::Tint(level.min_contrast()),
+                _ => bail!("Invalid target contrast {:?}", target),
+            }
+        } else {
+            Mode::BlackOrWhite
+        };
+
+        Ok(TextColor {
+            colors,
+            mode,
+            output: util::get_color_format(matches, "output-format")?,
+        })
     }
 
     fn run(&self, state: State) -> Result<()> {
-        terminal::text_colors(state, self.colors.iter().map(|&(c, _)| c))
+        let backgrounds = self.colors.iter().map(|&(c, _)| c);
+        match &self.mode {
+            Mode::BlackOrWhite => terminal::text_colors(state, backgrounds),
+            Mode::Candidates(candidates) => terminal::custom_text_colors(
+                state,
+                backgrounds.map(|background| {
+                    let (text, format) = best_candidate(background, candidates);
+                    (background, text, self.output.unwrap_or(format))
+                }),
+            ),
+            &Mode::Tint(target) => {
+                let pairs = backgrounds
+                    .map(|background| match tinted_text_color(background, target) {
+                        Some(text) => Ok((background, text, self.output.unwrap_or_default())),
+                        None => bail!(
+                            "There is no text color in the hue of {} with a contrast of {}",
+                            ColorFormat::Hex.format_or_hex(background),
+                            target,
+                        ),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                terminal::custom_text_colors(state, pairs)
+            }
+        }
     }
 }
+
+/// Returns the candidate with the highest contrast on the background
+fn best_candidate(background: Color, candidates: &[(Color, ColorFormat)]) -> (Color, ColorFormat) {
+    candidates
+        .iter()
+        .map(|&(c, f)| ((c, f), Algorithm::Wcag.contrast(c, background)))
+        .max_by(|(_, c1), (_, c2)| c1.total_cmp(c2))
+        .map(|(candidate, _)| candidate)
+        .unwrap()
+}
+
+/// Returns the text color with the hue and chroma of the background that is
+/// closest to the background, but still reaches the target contrast
+fn tinted_text_color(background: Color, target: f64) -> Option<Color> {
+    contrast::fix_contrast(Algorithm::Wcag, background, background, target, false)
+}
@@ -1,9 +1,17 @@
-use anyhow::{Context, Error, Result};
-use clap::ArgMatches;
-use std::iter;
+use anyhow::{bail, Context, Error, Result};
+use clap::{Arg, ArgMatches};
+use std::{iter, path::PathBuf};
 
 use crate::{
-    color::{self, Color, ColorFormat, ParseError},
+    color::{
+        self,
+        cvd::{Deficiency, Model, Simulation},
+        difference::DeltaE,
+        format::{AngleUnit, NumberFormat, RgbRange},
+        template::Template,
+        CodeFormat, Color, ColorFormat, CssFunction, ParseError,
+    },
+    export::{ImageOptions, MAX_IMAGE_SIZE},
     State,
 };
 
@@ -13,11 +21,302 @@ pub(super) fn get_color_format(
 ) -> Result<Option<ColorFormat>> {
     matches
         .value_of(arg_name)
-        .map(|v| match v.to_lowercase().as_str() {
-            "html" => Ok::<_, Error>(ColorFormat::Html),
-            "hex" => Ok(ColorFormat::Hex),
-            s => Ok(ColorFormat::Normal(s.parse()?)),
-        })
+        .map(parse_color_format)
+        .transpose()
+}
+
+/// Like `get_color_format`, but for arguments that accept several
+/// comma-separated formats
+pub(super) fn get_color_formats(matches: &ArgMatches, arg_name: &str) -> Result<Vec<ColorFormat>> {
+    matches
+        .values_of(arg_name)
+        .into_iter()
+        .flatten()
+        .map(parse_color_format)
+        .collect()
+}
+
+fn parse_color_format(s: &str) -> Result<ColorFormat> {
+    Ok(match s.to_lowercase().as_str() {
+        "html" => ColorFormat::Html,
+        "nearest" => ColorFormat::NearestName,
+        "hex" => ColorFormat::Hex,
+        "rust" => ColorFormat::Code(CodeFormat::Rust),
+        "c" => ColorFormat::Code(CodeFormat::C),
+        "swift" => ColorFormat::Code(CodeFormat::Swift),
+        "kotlin" => ColorFormat::Code(CodeFormat::Kotlin),
+        "flutter" => ColorFormat::Code(CodeFormat::Flutter),
+        "qt" => ColorFormat::Code(CodeFormat::Qt),
+        "latex" => ColorFormat::Code(CodeFormat::Latex),
+        "latex-rgb" => ColorFormat::Code(CodeFormat::LatexRgb),
+        "p3" => ColorFormat::Css(CssFunction::DisplayP3),
+        s => ColorFormat::Normal(s.parse()?),
+    })
+}
+
+/// If the `--css` flag is present, returns the CSS format that is closest to
+/// the given format
+pub(super) fn css_format(matches: &ArgMatches, format: ColorFormat) -> ColorFormat {
+    if matches.is_present("css") {
+        CssFunction::closest(format)
+    } else {
+        format
+    }
+}
+
+/// Arguments that control how numbers are formatted, see `get_number_format`
+pub(super) fn number_format_args<'a, 'b>() -> [Arg<'a, 'b>; 5] {
+    [
+        Arg::with_name("precision")
+            .long("precision")
+            .takes_value(true)
+            .help("Number of decimal digits in the output, from 0 to 15"),
+        Arg::with_name("rgb-range")
+            .long("rgb-range")
+            .takes_value(true)
+            .possible_values(&["255", "1", "100"])
+            .help("Range of RGB components: 0-255, 0-1 or 0%-100%"),
+        Arg::with_name("angle")
+            .long("angle")
+            .takes_value(true)
+            .possible_values(&["deg", "rad", "grad", "turn"])
+            .case_insensitive(true)
+            .help("Unit of hue angles"),
+        Arg::with_name("upper")
+            .long("upper")
+            .help("Print hex colors in uppercase"),
+        Arg::with_name("short-hex")
+            .long("short-hex")
+            .help("Print hex colors with 3 digits where possible, e.g. #f70"),
+    ]
+}
+
+pub(super) fn get_number_format(matches: &ArgMatches) -> Result<NumberFormat> {
+    let precision = match matches.value_of("precision") {
+        Some(s) => match s.parse::<usize>() {
+            Ok(precision) if precision <= 15 => Some(precision),
+            _ => bail!("Invalid precision {:?}, expected a number from 0 to 15", s),
+        },
+        None => None,
+    };
+    let rgb_range = match matches.value_of("rgb-range") {
+        Some("1") => RgbRange::Float,
+        Some("100") => RgbRange::Percent,
+        _ => RgbRange::Byte,
+    };
+    let angle = matches
+        .value_of("angle")
+        .map(|s| match s.to_lowercase().as_str() {
+            "rad" => AngleUnit::Rad,
+            "grad" => AngleUnit::Grad,
+            "turn" => AngleUnit::Turn,
+            _ => AngleUnit::Deg,
+        });
+
+    Ok(NumberFormat {
+        precision,
+        rgb_range,
+        upper_hex: matches.is_present("upper"),
+        short_hex: matches.is_present("short-hex"),
+        angle,
+    })
+}
+
+/// The `--export` argument, see `crate::export`
+pub(super) fn export_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("export")
+        .long("export")
+        .takes_value(true)
+        .value_name("file")
+        .help(
+            "Write a swatch sheet to a file instead of printing the colors. \
+            Supported file types: .svg, .html, and the image formats .ppm, .pam, .png",
+        )
+}
+
+pub(super) fn get_export_path(matches: &ArgMatches) -> Option<PathBuf> {
+    matches.value_of_os("export").map(PathBuf::from)
+}
+
+/// The `--width` and `--height` arguments for raster images
+pub(super) fn image_size_args<'a, 'b>() -> [Arg<'a, 'b>; 2] {
+    [
+        Arg::with_name("width")
+            .long("width")
+            .takes_value(true)
+            .help("Width of the exported image in pixels, from 1 to 10000"),
+        Arg::with_name("height")
+            .long("height")
+            .takes_value(true)
+            .help("Height of the exported image in pixels, from 1 to 10000"),
+    ]
+}
+
+pub(super) fn get_image_options(matches: &ArgMatches) -> Result<ImageOptions> {
+    Ok(ImageOptions {
+        width: matches
+            .value_of("width")
+            .map(parse_image_size)
+            .transpose()?,
+        height: matches
+            .value_of("height")
+            .map(parse_image_size)
+            .transpose()?,
+        dither: matches.is_present("dither"),
+    })
+}
+
+/// The `--daltonize` argument
+pub(super) fn daltonize_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("daltonize")
+        .long("daltonize")
+        .takes_value(true)
+        .value_name("type")
+        .possible_values(&["protanopia", "deuteranopia", "tritanopia"])
+        .help(
+            "Adjust the colors so they stay distinguishable for viewers with the given \
+            color vision deficiency",
+        )
+}
+
+pub(super) fn get_daltonize(matches: &ArgMatches) -> Option<Simulation> {
+    let deficiency = match matches.value_of("daltonize")? {
+        "protanopia" => Deficiency::Protan,
+        "deuteranopia" => Deficiency::Deutan,
+        _ => Deficiency::Tritan,
+    };
+    Some(Simulation::new(deficiency, 1.0, Model::Brettel))
+}
+
+const METRIC_HELP: &str = "\
+The color difference metric:
+
+* cie76: Euclidean distance in Lab
+* cie94: CIE94 for graphic arts
+* ciede2000: CIEDE2000 (default)
+* cmc: CMC 2:1 (acceptability)
+* cmc1:1: CMC 1:1 (imperceptibility)
+* ok: Euclidean distance in Oklab
+";
+
+/// The `--metric` argument for color differences
+pub(super) fn metric_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("metric")
+        .long("metric")
+        .short("m")
+        .takes_value(true)
+        .possible_values(&[
+            "cie76",
+            "76",
+            "cie94",
+            "94",
+            "ciede2000",
+            "2000",
+            "cmc",
+            "cmc2:1",
+            "cmc1:1",
+            "ok",
+        ])
+        .hide_possible_values(true)
+        .default_value("ciede2000")
+        .help(METRIC_HELP)
+}
+
+pub(super) fn get_metric(matches: &ArgMatches) -> Result<DeltaE> {
+    Ok(match matches.value_of("metric").unwrap() {
+        "cie76" | "76" => DeltaE::Cie76,
+        "cie94" | "94" => DeltaE::Cie94,
+        "ciede2000" | "2000" => DeltaE::Ciede2000,
+        "cmc" | "cmc2:1" => DeltaE::Cmc { l: 2.0, c: 1.0 },
+        "cmc1:1" => DeltaE::Cmc { l: 1.0, c: 1.0 },
+        "ok" => DeltaE::Ok,
+        m => bail!("Unknown metric {:?}", m),
+    })
+}
+
+const TYPE_HELP: &str = "\
+The color vision deficiencies to simulate. Several comma-separated types can be specified. \
+Defaults to protanopia, deuteranopia, tritanopia and achromatopsia.
+
+* protanopia, deuteranopia, tritanopia: missing L (red), M (green) or S (blue) cones
+* protanomaly, deuteranomaly, tritanomaly: anomalous cones, see --severity
+* achromatopsia: no color vision";
+
+/// The `--type`, `--model` and `--severity` arguments for simulating color
+/// vision deficiencies
+pub(super) fn simulation_args<'a, 'b>() -> [Arg<'a, 'b>; 3] {
+    [
+        Arg::with_name("type")
+            .long("type")
+            .short("t")
+            .takes_value(true)
+            .use_delimiter(true)
+            .possible_values(&[
+                "protanopia",
+                "deuteranopia",
+                "tritanopia",
+                "protanomaly",
+                "deuteranomaly",
+                "tritanomaly",
+                "achromatopsia",
+            ])
+            .hide_possible_values(true)
+            .help(TYPE_HELP),
+        Arg::with_name("model")
+            .long("model")
+            .takes_value(true)
+            .possible_values(&["brettel", "machado"])
+            .default_value("brettel")
+            .help(
+                "The simulation model: Brettel/Viénot (Viénot 1999 for protanopia \
+                and deuteranopia, Brettel 1997 for tritanopia) or Machado 2009",
+            ),
+        Arg::with_name("severity")
+            .long("severity")
+            .takes_value(true)
+            .default_value("0.6")
+            .help(
+                "The severity of protanomaly, deuteranomaly and tritanomaly, \
+                between 0 (normal vision) and 1 (dichromacy)",
+            ),
+    ]
+}
+
+pub(super) fn get_simulations(matches: &ArgMatches) -> Result<Vec<Simulation>> {
+    let model = match matches.value_of("model") {
+        Some("machado") => Model::Machado,
+        _ => Model::Brettel,
+    };
+    let severity = match matches.value_of("severity").unwrap().parse::<f64>() {
+        Ok(s) if (0.0..=1.0).contains(&s) => s,
+        _ => bail!("The severity must be a number between 0 and 1"),
+    };
+    Ok(match matches.values_of("type") {
+        Some(types) => types
+            .map(|t| {
+                let (deficiency, severity) = match t {
+                    "protanopia" => (Deficiency::Protan, 1.0),
+                    "deuteranopia" => (Deficiency::Deutan, 1.0),
+                    "tritanopia" => (Deficiency::Tritan, 1.0),
+                    "protanomaly" => (Deficiency::Protan, severity),
+                    "deuteranomaly" => (Deficiency::Deutan, severity),
+                    "tritanomaly" => (Deficiency::Tritan, severity),
+                    _ => (Deficiency::Achroma, 1.0),
+                };
+                Simulation::new(deficiency, severity, model)
+            })
+            .collect(),
+        None => Simulation::ALL
+            .iter()
+            .map(|s| Simulation::new(s.deficiency, s.severity, model))
+            .collect(),
+    })
+}
+
+pub(super) fn get_template(matches: &ArgMatches, arg_name: &str) -> Result<Option<Template>> {
+    matches
+        .value_of(arg_name)
+        .map(|v| v.parse::<Template>().map_err(Error::from))
         .transpose()
 }
 
@@ -36,3 +335,16 @@ pub(super) fn parse_size(s: &str) -> Result<u32> {
     s.parse()
         .with_context(|| format!("The size {:?} could not be parsed", s))
 }
+
+/// Parse the width or height of an exported image
+fn parse_image_size(s: &str) -> Result<u32> {
+    let size = parse_size(s)?;
+    if size == 0 || size > MAX_IMAGE_SIZE {
+        bail!(
+            "Invalid image size {:?}, expected a number from 1 to {}",
+            s,
+            MAX_IMAGE_SIZE
+        );
+    }
+    Ok(size)
+}
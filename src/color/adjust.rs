@@ -0,0 +1,112 @@
+//! Adjusting colors in a cylindrical color space (HSL, LCh or OKLCH).
+//!
+//! Amounts are percentages of the reference ranges used by CSS: 100% is a
+//! lightness of 1 in HSL and OKLCH or 100 in LCh, a saturation of 1 in HSL,
+//! and a chroma of 150 in LCh or 0.4 in OKLCH.
+
+use color_space::ToRgb;
+
+use super::{gamut, space::Rgb, Color, ColorSpace};
+
+/// An operation that changes a color
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Adjustment {
+    /// Increases the lightness by the percentage
+    Lighten(f64),
+    /// Decreases the lightness by the percentage
+    Darken(f64),
+    /// Increases the saturation or chroma by the percentage
+    Saturate(f64),
+    /// Decreases the saturation or chroma by the percentage
+    Desaturate(f64),
+    /// Rotates the hue by the angle in degrees
+    Rotate(f64),
+    /// Inverts the red, green and blue channels
+    Invert,
+}
+
+/// Applies the adjustments in the given order. `color_space` must be `Hsl`,
+/// `Lch` or `Oklch`. The result is mapped into the sRGB gamut.
+pub fn adjust(color: Color, adjustments: &[Adjustment], color_space: ColorSpace) -> Color {
+    let (lightness_range, chroma_range) = match color_space {
+        ColorSpace::Hsl => (1.0, 1.0),
+        ColorSpace::Lch => (100.0, 150.0),
+        ColorSpace::Oklch => (1.0, 0.4),
+        _ => panic!("Can't adjust colors in {:?}", color_space),
+    };
+    let to_components = |color: Color| {
+        let (_, components) = color.to_color_space(color_space).divide();
+        match color_space {
+            ColorSpace::Hsl => [components[2], components[1], components[0]],
+            _ => [components[0], components[1], components[2]],
+        }
+    };
+    let from_components = |[l, c, h]: [f64; 3]| {
+        let h = if h.is_nan() { 0.0 } else { h.rem_euclid(360.0) };
+        match color_space {
+            ColorSpace::Hsl => Color::new_unchecked(color_space, &[h, c, l]),
+            _ => Color::new_unchecked(color_space, &[l, c, h]),
+        }
+    };
+
+    let mut lch = to_components(color);
+    for &adjustment in adjustments {
+        let [l, c, h] = &mut lch;
+        match adjustment {
+            Adjustment::Lighten(p) => *l += p / 100.0 * lightness_range,
+            Adjustment::Darken(p) => *l -= p / 100.0 * lightness_range,
+            Adjustment::Saturate(p) => *c += p / 100.0 * chroma_range,
+            Adjustment::Desaturate(p) => *c -= p / 100.0 * chroma_range,
+            Adjustment::Rotate(deg) => *h += deg,
+            Adjustment::Invert => {
+                let rgb = from_components(lch).to_rgb();
+                let inverted = Rgb::new(255.0 - rgb.r, 255.0 - rgb.g, 255.0 - rgb.b);
+                lch = to_components(Color::Rgb(inverted));
+                continue;
+            }
+        }
+        *l = l.clamp(0.0, lightness_range);
+        *c = if color_space == ColorSpace::Hsl {
+            c.clamp(0.0, 1.0)
+        } else {
+            c.max(0.0)
+        };
+    }
+    gamut::map_to_gamut(from_components(lch), color_space)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adjust, Adjustment};
+    use crate::color::{space::Rgb, Color, ColorSpace};
+    use color_space::ToRgb;
+
+    fn hex(color: Color) -> [u8; 3] {
+        let rgb = color.to_rgb();
+        [rgb.r, rgb.g, rgb.b].map(|n| n.round() as u8)
+    }
+
+    #[test]
+    fn test_adjust() {
+        let red = Color::Rgb(Rgb::from_hex(0xff0000));
+
+        let lighter = adjust(red, &[Adjustment::Lighten(25.0)], ColorSpace::Hsl);
+        assert_eq!(hex(lighter), [255, 128, 128]);
+
+        let cyan = adjust(red, &[Adjustment::Rotate(180.0)], ColorSpace::Hsl);
+        assert_eq!(hex(cyan), [0, 255, 255]);
+
+        let gray = adjust(red, &[Adjustment::Desaturate(100.0)], ColorSpace::Hsl);
+        assert_eq!(hex(gray), [128, 128, 128]);
+
+        let inverted = adjust(red, &[Adjustment::Invert], ColorSpace::Oklch);
+        assert_eq!(hex(inverted), [0, 255, 255]);
+
+        // out of gamut colors are mapped into the gamut
+        let vivid = adjust(red, &[Adjustment::Saturate(50.0)], ColorSpace::Lch);
+        assert_eq!(hex(vivid), [255, 0, 0]);
+
+        let black = adjust(red, &[Adjustment::Darken(100.0)], ColorSpace::Oklch);
+        assert_eq!(hex(black), [0, 0, 0]);
+    }
+}
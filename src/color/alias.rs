@@ -0,0 +1,236 @@
+//! User-defined color aliases and palettes.
+//!
+//! Aliases are read from a config file, which contains one definition per
+//! line. Colors below a `[section]` header form a palette:
+//!
+//! ```text
+//! # Comments start with a hash sign
+//! highlight = #ff7700
+//!
+//! [brand]
+//! primary = hsl(30, 100%, 50%)
+//! secondary = rebeccapurple
+//! ```
+//!
+//! Palette colors are referenced as `brand.primary`, and `@brand` expands to
+//! all colors of the palette.
+//!
+//! The config file is `$COLO_CONFIG`, or `colo/colors.conf` in the user's
+//! config directory (e.g. `~/.config/colo/colors.conf`).
+
+use anyhow::{bail, Context, Result};
+use color_space::ToRgb;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use super::{hex, parse_literal, space::Rgb, Color};
+use crate::State;
+
+/// The aliases defined by the user, in the order in which they were defined.
+/// The names are lowercase; palette colors are prefixed with the palette name
+/// and a dot.
+static ALIASES: OnceLock<Vec<(String, Color)>> = OnceLock::new();
+
+/// Name of the config file in the user's config directory
+const CONFIG_FILE: &str = "colors.conf";
+
+/// Loads the aliases from the config file. If no path is given, the default
+/// location is used; it's not an error if that file doesn't exist.
+pub(crate) fn load(path: Option<&Path>, state: State) -> Result<()> {
+    let text = match path {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Could not read config file {:?}", path))?,
+        None => match default_path() {
+            Some(path) if path.is_file() => fs::read_to_string(&path)
+                .with_context(|| format!("Could not read config file {:?}", path))?,
+            _ => String::new(),
+        },
+    };
+
+    let aliases = parse_config(&text, state)?;
+    let _ = ALIASES.set(aliases);
+    Ok(())
+}
+
+/// Returns the default location of the config file
+fn default_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("colo").join(CONFIG_FILE))
+}
+
+/// Parses the content of a config file
+fn parse_config(text: &str, state: State) -> Result<Vec<(String, Color)>> {
+    let mut aliases: Vec<(String, Color)> = Vec::new();
+    let mut palette: Option<String> = None;
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[') {
+            let name = section
+                .strip_suffix(']')
+                .map(str::trim)
+                .with_context(|| format!("Line {}: Expected `]`", i + 1))?;
+            check_name(name).with_context(|| format!("Line {}: Invalid palette name", i + 1))?;
+            palette = Some(name.to_lowercase());
+            continue;
+        }
+
+        let (name, value) = match line.find('=') {
+            Some(idx) => (line[..idx].trim(), line[idx + 1..].trim()),
+            None => bail!("Line {}: Expected `name = color`", i + 1),
+        };
+        check_name(name).with_context(|| format!("Line {}: Invalid color name", i + 1))?;
+        if palette.is_none() && hex::parse(name).is_ok() {
+            bail!(
+                "Line {}: Invalid color name {:?}, it is also a hex color",
+                i + 1,
+                name
+            );
+        }
+
+        let colors = parse_literal(value, state)
+            .with_context(|| format!("Line {}: Invalid color", i + 1))?;
+        if colors.len() != 1 {
+            bail!("Line {}: Expected 1 color, got {}", i + 1, colors.len());
+        }
+
+        let name = match &palette {
+            Some(palette) => format!("{}.{}", palette, name.to_lowercase()),
+            None => name.to_lowercase(),
+        };
+        if aliases.iter().any(|(n, _)| *n == name) {
+            bail!("Line {}: The color {:?} is defined twice", i + 1, name);
+        }
+        aliases.push((name, colors[0].0));
+    }
+
+    Ok(aliases)
+}
+
+/// Checks that a color or palette name only contains letters, digits,
+/// underscores and dashes, and doesn't start with a digit.
+fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("The name is empty");
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|&c| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+    {
+        bail!("{:?} contains the invalid character {:?}", name, c);
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        bail!("{:?} starts with a digit", name);
+    }
+    Ok(())
+}
+
+/// Returns all user-defined colors
+pub fn all() -> &'static [(String, Color)] {
+    ALIASES.get().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Gets a user-defined color. The name (e.g. `Brand.Primary`) is converted to
+/// lowercase first.
+pub fn get(name: &str) -> Option<Color> {
+    let name = name.to_lowercase();
+    all()
+        .iter()
+        .find(|(k, _)| *k == name)
+        .map(|&(_, color)| color)
+}
+
+/// Gets all colors of a user-defined palette. If the palette doesn't exist, an
+/// empty list is returned.
+pub fn get_palette(name: &str) -> Vec<Color> {
+    let prefix = format!("{}.", name.to_lowercase());
+    all()
+        .iter()
+        .filter(|(k, _)| k.starts_with(&prefix))
+        .map(|&(_, color)| color)
+        .collect()
+}
+
+pub fn get_similar(name: &str) -> Vec<(&'static str, f64)> {
+    let name = name.to_lowercase();
+    all()
+        .iter()
+        .filter_map(|(k, _)| {
+            let score = strsim::jaro_winkler(k, &name);
+            if score > 0.85 {
+                Some((k.as_str(), score))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Gets the name of a user-defined color that is equal to the given color
+/// (after rounding to 8 bits per channel).
+pub fn get_name(color: Rgb) -> Option<&'static str> {
+    let hex = hex::rgb_to_u32(color);
+    all()
+        .iter()
+        .find(|(_, c)| hex::rgb_to_u32(c.to_rgb()) == hex)
+        .map(|(name, _)| name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_config;
+    use color_space::ToRgb;
+
+    use crate::{color::space::Rgb, State};
+
+    const STATE: State = State {
+        interactive: false,
+        color: false,
+        json: false,
+    };
+
+    #[test]
+    fn test_parse_config() {
+        let aliases = parse_config(
+            "# comment\n\
+            Highlight = #ff7700\n\
+            \n\
+            [brand]\n\
+            primary = rgb(0, 0, 255)\n\
+            dark-red = darkred\n",
+            STATE,
+        )
+        .unwrap();
+
+        let names: Vec<_> = aliases.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["highlight", "brand.primary", "brand.dark-red"]);
+        assert_eq!(aliases[1].1.to_rgb(), Rgb::new(0.0, 0.0, 255.0));
+    }
+
+    #[test]
+    fn test_parse_config_errors() {
+        assert!(parse_config("foo", STATE).is_err());
+        assert!(parse_config("foo.bar = red", STATE).is_err());
+        assert!(parse_config("[brand\nfoo = red", STATE).is_err());
+        assert!(parse_config("foo = red blue", STATE).is_err());
+        assert!(parse_config("foo = red\nFOO = blue", STATE).is_err());
+        assert!(parse_config("foo = rand", STATE).is_err());
+        assert!(parse_config("foo = -", STATE).is_err());
+        assert!(parse_config("foo = hsl(rand, 50%, 50%)", STATE).is_err());
+        assert!(parse_config("foo = red\nbar = foo", STATE).is_err());
+        // names that are hex colors would be shadowed
+        assert!(parse_config("bad = red", STATE).is_err());
+        assert!(parse_config("[p]\nbad = red", STATE).is_ok());
+    }
+}
@@ -2,9 +2,42 @@
 //!
 //! - https://www.w3.org/TR/2008/REC-WCAG20-20081211/#contrast-ratiodef
 //! - https://www.w3.org/TR/2008/REC-WCAG20-20081211/#relativeluminancedef
+//! - https://github.com/Myndex/apca-w3 (APCA 0.0.98G-4g)
 //!
 
-use super::space::Rgb;
+use color_space::ToRgb;
+
+use super::{
+    difference::DeltaE,
+    gamut,
+    space::{Lch, Rgb},
+    Color, ColorSpace,
+};
+
+/// A contrast algorithm
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Algorithm {
+    /// The WCAG 2 contrast ratio
+    Wcag,
+    /// The Accessible Perceptual Contrast Algorithm, proposed for WCAG 3
+    Apca,
+}
+
+impl Algorithm {
+    /// Returns the contrast of the text color on the background color. The
+    /// WCAG contrast ratio is symmetric, whereas the APCA lightness contrast
+    /// is positive for dark text on a light background and negative for
+    /// light text on a dark background.
+    pub(crate) fn contrast(self, text: Color, background: Color) -> f64 {
+        match self {
+            Algorithm::Wcag => contrast(text.relative_luminance(), background.relative_luminance()),
+            Algorithm::Apca => apca(
+                apca_luminance(text.to_rgb()),
+                apca_luminance(background.to_rgb()),
+            ),
+        }
+    }
+}
 
 /// Visible contrast between two colors, which is
 /// a value between 1 (no contrast) and 21 (high contrast).
@@ -31,7 +64,7 @@ pub(crate) fn relative_luminance(color: Rgb) -> f64 {
 }
 
 fn clamp_rgb(n: f64) -> f64 {
-    f64::max(0.0, f64::min(255.0, n))
+    n.clamp(0.0, 255.0)
 }
 
 #[allow(non_snake_case)]
@@ -42,3 +75,312 @@ fn get_RGB(n: f64) -> f64 {
         ((n + 0.055) / 1.055).powf(2.4)
     }
 }
+
+/// A WCAG 2 success criterion for contrast
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum WcagLevel {
+    /// Normal text, level AA (1.4.3)
+    Aa,
+    /// Normal text, level AAA (1.4.6)
+    Aaa,
+    /// Large text (at least 18pt, or 14pt bold), level AA (1.4.3)
+    AaLarge,
+    /// Large text, level AAA (1.4.6)
+    AaaLarge,
+    /// User interface components and graphical objects, level AA (1.4.11)
+    NonText,
+}
+
+impl WcagLevel {
+    pub(crate) const ALL: [WcagLevel; 5] = [
+        WcagLevel::Aa,
+        WcagLevel::Aaa,
+        WcagLevel::AaLarge,
+        WcagLevel::AaaLarge,
+        WcagLevel::NonText,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            WcagLevel::Aa => "AA",
+            WcagLevel::Aaa => "AAA",
+            WcagLevel::AaLarge => "AA-large",
+            WcagLevel::AaaLarge => "AAA-large",
+            WcagLevel::NonText => "non-text",
+        }
+    }
+
+    /// The minimum contrast ratio required by the success criterion
+    pub(crate) fn min_contrast(self) -> f64 {
+        match self {
+            WcagLevel::Aa | WcagLevel::AaaLarge => 4.5,
+            WcagLevel::Aaa => 7.0,
+            WcagLevel::AaLarge | WcagLevel::NonText => 3.0,
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        WcagLevel::ALL
+            .iter()
+            .copied()
+            .find(|level| level.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Finds the color closest to the text color (or the background color, if
+/// `fix_background` is true) that reaches the `target` contrast. The lightness
+/// is changed in the LCh color space, while the hue and chroma are kept, unless
+/// the chroma has to be reduced to stay in the sRGB gamut. With APCA, the
+/// target is the absolute Lc value.
+///
+/// Returns `None` if the target can't be reached.
+pub(crate) fn fix_contrast(
+    algorithm: Algorithm,
+    text: Color,
+    background: Color,
+    target: f64,
+    fix_background: bool,
+) -> Option<Color> {
+    let original = if fix_background { background } else { text };
+    let contrast = |color: Color| {
+        let c = if fix_background {
+            algorithm.contrast(text, color)
+        } else {
+            algorithm.contrast(color, background)
+        };
+        c.abs()
+    };
+    if contrast(original) >= target {
+        return Some(original);
+    }
+
+    let (_, lch) = original.to_color_space(ColorSpace::Lch).divide();
+    // grays should stay gray, despite rounding errors
+    let chroma = if lch[1] < 0.1 { 0.0 } else { lch[1] };
+    let with_lightness = |l: f64| {
+        let color = Color::Lch(Lch::new(l, chroma, lch[2]));
+        let rgb = gamut::map_to_gamut(color, ColorSpace::Lch).to_rgb();
+        if chroma == 0.0 {
+            let gray = ((rgb.r + rgb.g + rgb.b) / 3.0).round();
+            Color::Rgb(Rgb::new(gray, gray, gray))
+        } else {
+            Color::Rgb(Rgb::new(rgb.r.round(), rgb.g.round(), rgb.b.round()))
+        }
+    };
+
+    // search in both directions (darker and lighter)
+    [0.0, 100.0]
+        .iter()
+        .filter(|&&end| contrast(with_lightness(end)) >= target)
+        .flat_map(|&end| {
+            let (mut near, mut far) = (lch[0], end);
+            for _ in 0..32 {
+                let mid = (near + far) / 2.0;
+                if contrast(with_lightness(mid)) >= target {
+                    far = mid;
+                } else {
+                    near = mid;
+                }
+            }
+            // due to rounding, the color at the boundary isn't necessarily the
+            // closest one, so try a few more
+            let step = (end - lch[0]).signum() * 0.05;
+            (0..20).map(move |i| with_lightness((far + i as f64 * step).clamp(0.0, 100.0)))
+        })
+        .filter(|&color| contrast(color) >= target)
+        .map(|color| (color, DeltaE::Ciede2000.difference(original, color).total))
+        .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+        .map(|(color, _)| color)
+}
+
+/// The screen luminance used by APCA, which is similar to the relative
+/// luminance, but uses a simple power curve
+pub(crate) fn apca_luminance(color: Rgb) -> f64 {
+    let channel = |n: f64| (clamp_rgb(n) / 255.0).powf(2.4);
+    0.2126729 * channel(color.r) + 0.7151522 * channel(color.g) + 0.0721750 * channel(color.b)
+}
+
+/// The APCA lightness contrast (Lc) of text on a background, given their
+/// screen luminances. It is roughly between -108 and 106; positive values
+/// mean dark text on a light background.
+pub(crate) fn apca(text: f64, background: f64) -> f64 {
+    const BLACK_THRESHOLD: f64 = 0.022;
+    const BLACK_CLAMP: f64 = 1.414;
+    const SCALE: f64 = 1.14;
+    const OFFSET: f64 = 0.027;
+    const LOW_CLIP: f64 = 0.1;
+
+    let soft_clamp = |y: f64| {
+        if y < BLACK_THRESHOLD {
+            y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+        } else {
+            y
+        }
+    };
+    let text = soft_clamp(text);
+    let background = soft_clamp(background);
+
+    if (background - text).abs() < 0.0005 {
+        return 0.0;
+    }
+    let contrast = if background > text {
+        // dark text on a light background
+        let c = (background.powf(0.56) - text.powf(0.57)) * SCALE;
+        if c < LOW_CLIP {
+            0.0
+        } else {
+            c - OFFSET
+        }
+    } else {
+        // light text on a dark background
+        let c = (background.powf(0.65) - text.powf(0.62)) * SCALE;
+        if c > -LOW_CLIP {
+            0.0
+        } else {
+            c + OFFSET
+        }
+    };
+    contrast * 100.0
+}
+
+/// The font weights of the APCA font lookup table
+pub(crate) const APCA_FONT_WEIGHTS: [u32; 9] = [100, 200, 300, 400, 500, 600, 700, 800, 900];
+
+/// Only usable for non-text elements
+const NON_TEXT: f64 = 777.0;
+/// Not usable at all
+const UNUSABLE: f64 = 999.0;
+
+/// The APCA font lookup table: For each Lc value (in steps of 5, starting at
+/// 15), the minimum font size in px for each font weight
+#[rustfmt::skip]
+const APCA_FONT_SIZES: [[f64; 9]; 23] = [
+    [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0], // 15
+    [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0], // 20
+    [777.0, 777.0, 777.0, 120.0, 120.0, 108.0, 96.0, 96.0, 96.0],    // 25
+    [777.0, 777.0, 120.0, 108.0, 108.0, 96.0, 72.0, 72.0, 72.0],     // 30
+    [777.0, 120.0, 108.0, 96.0, 72.0, 60.0, 48.0, 48.0, 48.0],       // 35
+    [120.0, 108.0, 96.0, 60.0, 48.0, 42.0, 32.0, 32.0, 32.0],        // 40
+    [108.0, 96.0, 72.0, 42.0, 32.0, 28.0, 24.0, 24.0, 24.0],         // 45
+    [96.0, 72.0, 60.0, 32.0, 28.0, 24.0, 21.0, 21.0, 21.0],          // 50
+    [80.0, 60.0, 48.0, 28.0, 24.0, 21.0, 18.0, 18.0, 18.0],          // 55
+    [72.0, 48.0, 42.0, 24.0, 21.0, 18.0, 16.0, 16.0, 18.0],          // 60
+    [68.0, 46.0, 32.0, 21.75, 19.0, 17.0, 15.0, 16.0, 18.0],         // 65
+    [64.0, 44.0, 28.0, 19.5, 18.0, 16.0, 14.5, 16.0, 18.0],          // 70
+    [60.0, 42.0, 24.0, 18.0, 16.0, 15.0, 14.0, 16.0, 18.0],          // 75
+    [56.0, 38.25, 23.0, 17.25, 15.81, 14.81, 14.0, 16.0, 18.0],      // 80
+    [52.0, 34.5, 22.0, 16.5, 15.625, 14.625, 14.0, 16.0, 18.0],      // 85
+    [48.0, 32.0, 21.0, 16.0, 15.5, 14.5, 14.0, 16.0, 18.0],          // 90
+    [45.0, 28.0, 19.5, 15.5, 15.0, 14.0, 13.5, 16.0, 18.0],          // 95
+    [42.0, 26.5, 18.5, 15.0, 14.5, 13.5, 13.0, 16.0, 18.0],          // 100
+    [39.0, 25.0, 18.0, 14.5, 14.0, 13.0, 12.0, 16.0, 18.0],          // 105
+    [36.0, 24.0, 17.0, 14.0, 13.0, 12.0, 11.0, 16.0, 18.0],          // 110
+    [34.0, 22.0, 16.0, 13.5, 12.5, 11.5, 10.5, 16.0, 18.0],          // 115
+    [32.0, 21.0, 15.0, 13.0, 12.0, 11.0, 10.0, 16.0, 18.0],          // 120
+    [30.0, 20.0, 14.5, 12.5, 11.5, 10.5, 9.5, 16.0, 18.0],           // 125
+];
+
+/// What a color combination with a certain APCA contrast can be used for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ApcaUsage {
+    /// Text with the minimum font size (in px) for each font weight in
+    /// `APCA_FONT_WEIGHTS`, or `None` if the weight is too thin
+    Text([Option<f64>; 9]),
+    /// Only non-text elements, e.g. dividers or disabled controls
+    NonText,
+    /// Neither text nor non-text elements
+    Insufficient,
+}
+
+/// Looks up the usage for an APCA contrast in the font lookup table. The
+/// polarity of the contrast is ignored.
+pub(crate) fn apca_usage(lc: f64) -> ApcaUsage {
+    let lc = lc.abs();
+    if lc < 15.0 {
+        return ApcaUsage::Insufficient;
+    }
+    let row = (((lc - 15.0) / 5.0) as usize).min(APCA_FONT_SIZES.len() - 1);
+    let sizes = APCA_FONT_SIZES[row];
+    if sizes.iter().all(|&s| s == NON_TEXT || s == UNUSABLE) {
+        return ApcaUsage::NonText;
+    }
+    ApcaUsage::Text(sizes.map(|s| if s >= NON_TEXT { None } else { Some(s) }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apca, apca_luminance, apca_usage, fix_contrast, ApcaUsage, WcagLevel};
+    use crate::color::{space::Rgb, Algorithm, Color};
+    use color_space::ToRgb;
+
+    #[test]
+    fn test_apca() {
+        let lc = |text: u32, bg: u32| {
+            apca(
+                apca_luminance(Rgb::from_hex(text)),
+                apca_luminance(Rgb::from_hex(bg)),
+            )
+        };
+        let data = [
+            (0x000000, 0xffffff, 106.04),
+            (0xffffff, 0x000000, -107.88),
+            (0x888888, 0xffffff, 63.06),
+            (0xffffff, 0x888888, -68.54),
+            (0x000000, 0xaaaaaa, 58.15),
+            (0xaaaaaa, 0x000000, -56.24),
+        ];
+        for (text, bg, expected) in data {
+            let lc = lc(text, bg);
+            assert!((lc - expected).abs() < 0.01, "{} != {}", lc, expected);
+        }
+
+        assert_eq!(apca_usage(10.0), ApcaUsage::Insufficient);
+        assert_eq!(apca_usage(-18.0), ApcaUsage::NonText);
+        match apca_usage(63.06) {
+            ApcaUsage::Text(sizes) => {
+                assert_eq!(sizes[3], Some(24.0));
+                assert_eq!(sizes[6], Some(16.0));
+            }
+            usage => panic!("unexpected {:?}", usage),
+        }
+    }
+
+    #[test]
+    fn test_wcag_level() {
+        assert_eq!(WcagLevel::from_name("aa"), Some(WcagLevel::Aa));
+        assert_eq!(WcagLevel::from_name("AAA-Large"), Some(WcagLevel::AaaLarge));
+        assert_eq!(WcagLevel::from_name("A"), None);
+        assert_eq!(
+            WcagLevel::AaaLarge.min_contrast(),
+            WcagLevel::Aa.min_contrast()
+        );
+    }
+
+    #[test]
+    fn test_fix_contrast() {
+        let gray = Color::Rgb(Rgb::new(119.0, 119.0, 119.0));
+        let white = Color::Rgb(Rgb::new(255.0, 255.0, 255.0));
+        let orange = Color::Rgb(Rgb::new(255.0, 165.0, 0.0));
+
+        let fixed = fix_contrast(Algorithm::Wcag, gray, white, 4.5, false).unwrap();
+        let contrast = Algorithm::Wcag.contrast(fixed, white);
+        assert!((4.5..4.6).contains(&contrast), "{}", contrast);
+        // grays stay gray
+        let rgb = fixed.to_rgb();
+        assert!(rgb.r == rgb.g && rgb.g == rgb.b, "{:?}", rgb);
+        let fixed = fix_contrast(Algorithm::Wcag, white, white, 4.5, false).unwrap();
+        let rgb = fixed.to_rgb();
+        assert!(rgb.r == rgb.g && rgb.g == rgb.b, "{:?}", rgb);
+
+        let fixed = fix_contrast(Algorithm::Apca, orange, white, 60.0, false).unwrap();
+        assert!(Algorithm::Apca.contrast(fixed, white) >= 60.0);
+
+        let fixed = fix_contrast(Algorithm::Wcag, white, orange, 4.5, true).unwrap();
+        assert!(Algorithm::Wcag.contrast(white, fixed) >= 4.5);
+
+        assert_eq!(
+            fix_contrast(Algorithm::Wcag, gray, white, 22.0, false),
+            None
+        );
+    }
+}
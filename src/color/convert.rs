@@ -31,6 +31,8 @@ impl TryFrom<(ColorSpace, &[f64])> for Color {
             ColorSpace::Xyz => Color::try_from(Xyz::new(vals[0], vals[1], vals[2])),
             ColorSpace::Yxy => Color::try_from(Yxy::new(vals[0], vals[1], vals[2])),
             ColorSpace::Gray => Color::try_from(Gray::new(vals[0])),
+            ColorSpace::Oklab => Color::try_from(Oklab::new(vals[0], vals[1], vals[2])),
+            ColorSpace::Oklch => Color::try_from(Oklch::new(vals[0], vals[1], vals[2])),
         }
     }
 }
@@ -39,11 +41,13 @@ pub(crate) fn color_from_components_unchecked(space: ColorSpace, vals: &[f64]) -
     let required_args = space.num_components();
 
     if vals.len() != required_args {
-        Err::<(), _>(ParseError::NumberOfComponents {
-            expected: required_args,
-            got: vals.len(),
-        })
-        .unwrap();
+        panic!(
+            "{}",
+            ParseError::NumberOfComponents {
+                expected: required_args,
+                got: vals.len(),
+            }
+        );
     }
 
     // Create the color and check if the values are in the valid range
@@ -60,6 +64,8 @@ pub(crate) fn color_from_components_unchecked(space: ColorSpace, vals: &[f64]) -
         ColorSpace::Xyz => Color::Xyz(Xyz::new(vals[0], vals[1], vals[2])),
         ColorSpace::Yxy => Color::Yxy(Yxy::new(vals[0], vals[1], vals[2])),
         ColorSpace::Gray => Color::Gray(Gray::new(vals[0])),
+        ColorSpace::Oklab => Color::Oklab(Oklab::new(vals[0], vals[1], vals[2])),
+        ColorSpace::Oklch => Color::Oklch(Oklch::new(vals[0], vals[1], vals[2])),
     }
 }
 
@@ -129,6 +135,14 @@ try_from_color! { Yxy ->
 try_from_color! { Gray ->
     l: 0.0 to 1.0;
 }
+try_from_color! { Oklab ->
+    l: 0.0 to 1.0;
+}
+try_from_color! { Oklch ->
+    l: 0.0 to 1.0;
+    c: 0.0 to 0.5;
+    h: -360.0 to 360.0;
+}
 
 /// Checks that the value is in the specified range. If it isn't, an error is
 /// returned.
@@ -166,6 +180,8 @@ macro_rules! from_color_for {
                     Color::Xyz(c) => c.into(),
                     Color::Yxy(c) => c.into(),
                     Color::Gray(c) => c.into(),
+                    Color::Oklab(c) => c.into(),
+                    Color::Oklch(c) => c.into(),
                 }
             }
         }
@@ -184,3 +200,5 @@ from_color_for!(HunterLab);
 from_color_for!(Xyz);
 from_color_for!(Yxy);
 from_color_for!(Gray);
+from_color_for!(Oklab);
+from_color_for!(Oklch);
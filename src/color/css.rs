@@ -0,0 +1,335 @@
+//! Serialization of colors as CSS Color Level 4 functions, e.g.
+//! `rgb(255 119 0)`, `hsl(28deg 100% 50%)` or `color(display-p3 1 0.5 0.1)`.
+//!
+//! - https://www.w3.org/TR/css-color-4/
+//!
+//! Note that CSS defines `lab()` and `lch()` with a D50 white point, whereas
+//! colo's Lab and LCh color spaces use D65. The values are converted
+//! accordingly.
+
+use color_space::ToRgb;
+
+use super::{
+    format::{NumberFormat, RgbRange},
+    html,
+    oklab::{linear_to_srgb, srgb_to_linear},
+    space::Rgb,
+    Color, ColorFormat, ColorSpace,
+};
+
+/// A CSS color function
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CssFunction {
+    /// A named color such as `orange`. Not all colors have a name.
+    Named,
+    Rgb,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    /// `color(display-p3 r g b)`
+    DisplayP3,
+    /// `color(xyz-d65 x y z)`
+    XyzD65,
+}
+
+impl CssFunction {
+    /// Returns the CSS format that is closest to the given format. Formats that
+    /// are valid CSS (such as hex colors) are returned unchanged.
+    pub fn closest(format: ColorFormat) -> ColorFormat {
+        let function = match format {
+            ColorFormat::Hex | ColorFormat::Css(_) => return format,
+            ColorFormat::Html | ColorFormat::NearestName => CssFunction::Named,
+            ColorFormat::Code(_) => CssFunction::Rgb,
+            ColorFormat::Normal(space) => match space {
+                ColorSpace::Rgb | ColorSpace::Cmy | ColorSpace::Cmyk => CssFunction::Rgb,
+                ColorSpace::Hsl | ColorSpace::Gray => CssFunction::Hsl,
+                ColorSpace::Hsv => CssFunction::Hwb,
+                ColorSpace::Lab | ColorSpace::HunterLab | ColorSpace::Luv => CssFunction::Lab,
+                ColorSpace::Lch => CssFunction::Lch,
+                ColorSpace::Xyz | ColorSpace::Yxy => CssFunction::XyzD65,
+                ColorSpace::Oklab => CssFunction::Oklab,
+                ColorSpace::Oklch => CssFunction::Oklch,
+            },
+        };
+        ColorFormat::Css(function)
+    }
+
+    /// Formats the color. Returns `None` if the color has no name and the
+    /// function is `CssFunction::Named`.
+    pub fn format(&self, color: Color, num: NumberFormat) -> Option<String> {
+        Some(match *self {
+            CssFunction::Named => html::get_name(color.to_rgb())?.to_string(),
+            CssFunction::Rgb => {
+                let rgb = color.to_rgb();
+                let [r, g, b] = num.rgb([rgb.r, rgb.g, rgb.b].map(|n| n.clamp(0.0, 255.0)));
+                match num.rgb_range {
+                    // rgb() doesn't support numbers between 0 and 1
+                    RgbRange::Float => format!("color(srgb {} {} {})", r, g, b),
+                    _ => format!("rgb({} {} {})", r, g, b),
+                }
+            }
+            CssFunction::Hsl => {
+                let (_, v) = color.to_color_space(ColorSpace::Hsl).divide();
+                format!(
+                    "hsl({} {}% {}%)",
+                    num.angle(v[0], true),
+                    num.round(v[1] * 100.0, 1),
+                    num.round(v[2] * 100.0, 1),
+                )
+            }
+            CssFunction::Hwb => {
+                let (_, v) = color.to_color_space(ColorSpace::Hsv).divide();
+                let (h, s, v) = (v[0], v[1], v[2]);
+                format!(
+                    "hwb({} {}% {}%)",
+                    num.angle(h, true),
+                    num.round((1.0 - s) * v * 100.0, 1),
+                    num.round((1.0 - v) * 100.0, 1),
+                )
+            }
+            CssFunction::Lab => {
+                let [l, a, b] = lab_d50(color);
+                format!(
+                    "lab({}% {} {})",
+                    num.round(l, 2),
+                    num.round(a, 2),
+                    num.round(b, 2)
+                )
+            }
+            CssFunction::Lch => {
+                let [l, a, b] = lab_d50(color);
+                let (c, h) = to_polar(a, b);
+                format!(
+                    "lch({}% {} {})",
+                    num.round(l, 2),
+                    num.round(c, 2),
+                    num.angle(h, true),
+                )
+            }
+            CssFunction::Oklab => {
+                let (_, v) = color.to_color_space(ColorSpace::Oklab).divide();
+                format!(
+                    "oklab({}% {} {})",
+                    num.round(v[0] * 100.0, 2),
+                    num.round(v[1], 4),
+                    num.round(v[2], 4),
+                )
+            }
+            CssFunction::Oklch => {
+                let (_, v) = color.to_color_space(ColorSpace::Oklch).divide();
+                format!(
+                    "oklch({}% {} {})",
+                    num.round(v[0] * 100.0, 2),
+                    num.round(v[1], 4),
+                    num.angle(v[2], true),
+                )
+            }
+            CssFunction::DisplayP3 => {
+                let [x, y, z] = xyz_d65(color);
+                let [r, g, b] = mul(XYZ_TO_LINEAR_P3, [x, y, z]).map(linear_to_srgb);
+                format!(
+                    "color(display-p3 {} {} {})",
+                    num.round(r, 4),
+                    num.round(g, 4),
+                    num.round(b, 4),
+                )
+            }
+            CssFunction::XyzD65 => {
+                let [x, y, z] = xyz_d65(color);
+                format!(
+                    "color(xyz-d65 {} {} {})",
+                    num.round(x, 4),
+                    num.round(y, 4),
+                    num.round(z, 4),
+                )
+            }
+        })
+    }
+}
+
+const LINEAR_SRGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+const XYZ_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077204, 0.0415550574071756],
+    [
+        0.05563007969699364,
+        -0.20397695888897652,
+        1.0569715142428784,
+    ],
+];
+
+const XYZ_TO_LINEAR_P3: [[f64; 3]; 3] = [
+    [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+    [
+        -0.8294889695615747,
+        1.7626640603183463,
+        0.023624685841943577,
+    ],
+    [
+        0.03584583024378447,
+        -0.07617238926804182,
+        0.9568845240076872,
+    ],
+];
+
+/// Bradford chromatic adaptation from D65 to D50
+const D65_TO_D50: [[f64; 3]; 3] = [
+    [
+        1.0479298208405488,
+        0.022946793341019088,
+        -0.05019222954313557,
+    ],
+    [
+        0.029627815688159344,
+        0.990434484573249,
+        -0.01707382502938514,
+    ],
+    [
+        -0.009243058152591178,
+        0.015055144896577895,
+        0.7518742899580008,
+    ],
+];
+
+/// Bradford chromatic adaptation from D50 to D65
+const D50_TO_D65: [[f64; 3]; 3] = [
+    [
+        0.9554733942048979,
+        -0.023098374726038654,
+        0.06325919498911496,
+    ],
+    [
+        -0.02836971286639444,
+        1.0099953374555604,
+        0.02104147560735432,
+    ],
+    [
+        0.012314034948960157,
+        -0.02050758481440557,
+        1.3303659126444374,
+    ],
+];
+
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+fn mul(m: [[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// Returns the CIE XYZ coordinates (D65) of the color, where Y is between 0
+/// and 1
+fn xyz_d65(color: Color) -> [f64; 3] {
+    let rgb = color.to_rgb();
+    let linear = [rgb.r, rgb.g, rgb.b].map(|n| srgb_to_linear(n / 255.0));
+    mul(LINEAR_SRGB_TO_XYZ, linear)
+}
+
+const EPSILON: f64 = 216.0 / 24389.0;
+const KAPPA: f64 = 24389.0 / 27.0;
+
+/// Returns the CIELAB coordinates of the color with a D50 white point
+pub(crate) fn lab_d50(color: Color) -> [f64; 3] {
+    let xyz = mul(D65_TO_D50, xyz_d65(color));
+    let [fx, fy, fz] = [0, 1, 2].map(|i| {
+        let t = xyz[i] / D50_WHITE[i];
+        if t > EPSILON {
+            t.cbrt()
+        } else {
+            (KAPPA * t + 16.0) / 116.0
+        }
+    });
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Returns the color with the given CIELAB coordinates with a D50 white point.
+/// Colors outside of the sRGB gamut are clipped.
+pub(crate) fn from_lab_d50([l, a, b]: [f64; 3]) -> Color {
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+    let from_f = |f: f64| {
+        if f.powi(3) > EPSILON {
+            f.powi(3)
+        } else {
+            (116.0 * f - 16.0) / KAPPA
+        }
+    };
+    let y = if l > KAPPA * EPSILON {
+        fy.powi(3)
+    } else {
+        l / KAPPA
+    };
+    let xyz = [from_f(fx), y, from_f(fz)];
+    let xyz = [0, 1, 2].map(|i| xyz[i] * D50_WHITE[i]);
+
+    let linear = mul(XYZ_TO_LINEAR_SRGB, mul(D50_TO_D65, xyz));
+    let [r, g, b] = linear.map(|n| (linear_to_srgb(n) * 255.0).clamp(0.0, 255.0));
+    Color::Rgb(Rgb::new(r, g, b))
+}
+
+/// Converts `a` and `b` to chroma and hue (in degrees)
+fn to_polar(a: f64, b: f64) -> (f64, f64) {
+    let c = (a * a + b * b).sqrt();
+    // the hue is meaningless for achromatic colors
+    let h = if c < 1e-4 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    };
+    (c, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use color_space::ToRgb;
+
+    use super::CssFunction;
+    use crate::color::{format::NumberFormat, space::Rgb, Color};
+
+    fn format(function: CssFunction, color: Color) -> String {
+        function.format(color, NumberFormat::default()).unwrap()
+    }
+
+    #[test]
+    fn test_css_format() {
+        let orange = Color::Rgb(Rgb::new(255.0, 119.0, 0.0));
+        let white = Color::Rgb(Rgb::new(255.0, 255.0, 255.0));
+
+        assert_eq!(format(CssFunction::Rgb, orange), "rgb(255 119 0)");
+        assert_eq!(format(CssFunction::Hsl, orange), "hsl(28deg 100% 50%)");
+        assert_eq!(format(CssFunction::Hwb, orange), "hwb(28deg 0% 0%)");
+        assert_eq!(format(CssFunction::Lab, white), "lab(100% 0 0)");
+        assert_eq!(format(CssFunction::Lch, white), "lch(100% 0 0deg)");
+        assert_eq!(
+            format(CssFunction::DisplayP3, white),
+            "color(display-p3 1 1 1)"
+        );
+        assert_eq!(format(CssFunction::Oklch, white), "oklch(100% 0 0deg)");
+        assert_eq!(
+            CssFunction::Named.format(orange, NumberFormat::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_lab_d50() {
+        let orange = Color::Rgb(Rgb::new(255.0, 119.0, 0.0));
+        let [l, a, b] = super::lab_d50(orange);
+        assert!((l - 66.12).abs() < 0.05, "{}", l);
+        assert!((a - 49.64).abs() < 0.05, "{}", a);
+        assert!((b - 74.0).abs() < 0.05, "{}", b);
+
+        let rgb = super::from_lab_d50([l, a, b]).to_rgb();
+        assert!((rgb.r - 255.0).abs() < 1e-6, "{:?}", rgb);
+        assert!((rgb.g - 119.0).abs() < 1e-6, "{:?}", rgb);
+        assert!(rgb.b.abs() < 1e-6, "{:?}", rgb);
+    }
+}
@@ -0,0 +1,276 @@
+//! Simulation of color vision deficiencies (CVD).
+//!
+//! All simulations operate on linear sRGB values:
+//!
+//! - Protanopia and deuteranopia: Viénot, Brettel & Mollon (1999)
+//! - Tritanopia: Brettel, Viénot & Mollon (1997)
+//! - Machado, Oliveira & Fernandes (2009)
+//!
+//! The matrices are taken from <https://github.com/DaltonLens/libDaltonLens>.
+//! Anomalous trichromacy is simulated by interpolating between the original
+//! and the simulated dichromatic color.
+//!
+//! Daltonization follows Fidaner, Lin & Ozguven (2005): The difference between
+//! the original and the simulated color, which the viewer can't see, is
+//! shifted into the channels that the viewer can perceive.
+
+use color_space::ToRgb;
+use std::fmt;
+
+use super::{
+    oklab::{linear_to_srgb, srgb_to_linear},
+    space::Rgb,
+    Color,
+};
+
+type Matrix = [[f64; 3]; 3];
+
+/// The type of cone that is missing or anomalous
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Deficiency {
+    /// L cones (red)
+    Protan,
+    /// M cones (green)
+    Deutan,
+    /// S cones (blue)
+    Tritan,
+    /// No color vision at all
+    Achroma,
+}
+
+/// The simulation model
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Model {
+    /// Viénot 1999 for protanopia and deuteranopia, Brettel 1997 for
+    /// tritanopia
+    Brettel,
+    /// Machado 2009
+    Machado,
+}
+
+/// A simulated color vision deficiency
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Simulation {
+    pub deficiency: Deficiency,
+    /// The severity between 0 (normal vision) and 1 (dichromacy)
+    pub severity: f64,
+    pub model: Model,
+}
+
+impl Simulation {
+    /// The dichromacies and achromatopsia
+    pub const ALL: [Simulation; 4] = [
+        Simulation::new(Deficiency::Protan, 1.0, Model::Brettel),
+        Simulation::new(Deficiency::Deutan, 1.0, Model::Brettel),
+        Simulation::new(Deficiency::Tritan, 1.0, Model::Brettel),
+        Simulation::new(Deficiency::Achroma, 1.0, Model::Brettel),
+    ];
+
+    pub const fn new(deficiency: Deficiency, severity: f64, model: Model) -> Self {
+        Simulation {
+            deficiency,
+            severity,
+            model,
+        }
+    }
+
+    /// Returns how the color is perceived with this color vision deficiency
+    pub fn simulate(&self, color: Color) -> Color {
+        let rgb = color.to_rgb();
+        let linear = [rgb.r, rgb.g, rgb.b].map(|n| srgb_to_linear(n.clamp(0.0, 255.0) / 255.0));
+
+        let simulated = match (self.deficiency, self.model) {
+            (Deficiency::Achroma, _) => {
+                let y = 0.2126 * linear[0] + 0.7152 * linear[1] + 0.0722 * linear[2];
+                [y, y, y]
+            }
+            (Deficiency::Protan, Model::Brettel) => mul(VIENOT_PROTAN, linear),
+            (Deficiency::Deutan, Model::Brettel) => mul(VIENOT_DEUTAN, linear),
+            (Deficiency::Tritan, Model::Brettel) => {
+                let [n0, n1, n2] = BRETTEL_TRITAN_NORMAL;
+                let dot = n0 * linear[0] + n1 * linear[1] + n2 * linear[2];
+                let matrix = if dot >= 0.0 {
+                    BRETTEL_TRITAN_1
+                } else {
+                    BRETTEL_TRITAN_2
+                };
+                mul(matrix, linear)
+            }
+            (Deficiency::Protan, Model::Machado) => mul(MACHADO_PROTAN, linear),
+            (Deficiency::Deutan, Model::Machado) => mul(MACHADO_DEUTAN, linear),
+            (Deficiency::Tritan, Model::Machado) => mul(MACHADO_TRITAN, linear),
+        };
+
+        let severity = self.severity.clamp(0.0, 1.0);
+        let [r, g, b] = [0, 1, 2].map(|i| {
+            let n = linear[i] + (simulated[i] - linear[i]) * severity;
+            linear_to_srgb(n.clamp(0.0, 1.0)) * 255.0
+        });
+        Color::Rgb(Rgb::new(r, g, b))
+    }
+}
+
+impl Simulation {
+    /// Adjusts the color so that it stays distinguishable for viewers with
+    /// this color vision deficiency. Achromatopsia can't be corrected.
+    pub fn daltonize(&self, color: Color) -> Color {
+        let shift = match self.deficiency {
+            Deficiency::Protan | Deficiency::Deutan => DALTONIZE_RED_GREEN,
+            Deficiency::Tritan => DALTONIZE_BLUE_YELLOW,
+            Deficiency::Achroma => return color,
+        };
+        let rgb = color.to_rgb();
+        let simulated = self.simulate(color).to_rgb();
+        let error = [
+            rgb.r - simulated.r,
+            rgb.g - simulated.g,
+            rgb.b - simulated.b,
+        ];
+        let [r, g, b] = mul(shift, error);
+        Color::Rgb(Rgb::new(
+            (rgb.r + r).clamp(0.0, 255.0),
+            (rgb.g + g).clamp(0.0, 255.0),
+            (rgb.b + b).clamp(0.0, 255.0),
+        ))
+    }
+}
+
+impl fmt::Display for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.deficiency {
+            Deficiency::Protan => "protan",
+            Deficiency::Deutan => "deuteran",
+            Deficiency::Tritan => "tritan",
+            Deficiency::Achroma => return f.write_str("achromatopsia"),
+        };
+        if self.severity >= 1.0 {
+            write!(f, "{}opia", name)
+        } else {
+            write!(f, "{}omaly ({:.0}%)", name, self.severity * 100.0)
+        }
+    }
+}
+
+fn mul(m: Matrix, v: [f64; 3]) -> [f64; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// Shifts the red error into the green and blue channels
+const DALTONIZE_RED_GREEN: Matrix = [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]];
+
+/// Shifts the blue error into the red and green channels
+const DALTONIZE_BLUE_YELLOW: Matrix = [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]];
+
+const VIENOT_PROTAN: Matrix = [
+    [0.11238, 0.88762, 0.00000],
+    [0.11238, 0.88762, -0.00000],
+    [0.00401, -0.00401, 1.00000],
+];
+
+const VIENOT_DEUTAN: Matrix = [
+    [0.29275, 0.70725, 0.00000],
+    [0.29275, 0.70725, -0.00000],
+    [-0.02234, 0.02234, 1.00000],
+];
+
+const BRETTEL_TRITAN_1: Matrix = [
+    [1.01277, 0.13548, -0.14826],
+    [-0.01243, 0.86812, 0.14431],
+    [0.07589, 0.80500, 0.11911],
+];
+
+const BRETTEL_TRITAN_2: Matrix = [
+    [0.93678, 0.18979, -0.12657],
+    [0.06154, 0.81526, 0.12320],
+    [-0.37562, 1.12767, 0.24796],
+];
+
+/// The normal of the plane separating the two half-planes of the Brettel
+/// tritanopia simulation
+const BRETTEL_TRITAN_NORMAL: [f64; 3] = [0.03901, -0.02788, -0.01113];
+
+const MACHADO_PROTAN: Matrix = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+
+const MACHADO_DEUTAN: Matrix = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+
+const MACHADO_TRITAN: Matrix = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{Deficiency, Model, Simulation};
+    use crate::color::{difference::DeltaE, space::Rgb, Color};
+    use color_space::ToRgb;
+
+    fn simulate(sim: Simulation, hex: u32) -> [u8; 3] {
+        let rgb = sim.simulate(Color::Rgb(Rgb::from_hex(hex))).to_rgb();
+        [rgb.r, rgb.g, rgb.b].map(|n| n.round() as u8)
+    }
+
+    #[test]
+    fn test_cvd() {
+        for model in [Model::Brettel, Model::Machado] {
+            for deficiency in [
+                Deficiency::Protan,
+                Deficiency::Deutan,
+                Deficiency::Tritan,
+                Deficiency::Achroma,
+            ] {
+                let sim = Simulation::new(deficiency, 1.0, model);
+                // white and black are perceived normally
+                assert_eq!(simulate(sim, 0xffffff), [255, 255, 255], "{}", sim);
+                assert_eq!(simulate(sim, 0x000000), [0, 0, 0], "{}", sim);
+
+                let normal = Simulation::new(deficiency, 0.0, model);
+                assert_eq!(simulate(normal, 0xff7700), [255, 119, 0]);
+            }
+        }
+
+        // red and green are hard to distinguish for protanopes and deuteranopes
+        let protan = Simulation::new(Deficiency::Protan, 1.0, Model::Brettel);
+        let [r, g, _] = simulate(protan, 0xff0000);
+        assert!(r.abs_diff(g) < 10, "{} {}", r, g);
+
+        let achroma = Simulation::new(Deficiency::Achroma, 1.0, Model::Brettel);
+        let [r, g, b] = simulate(achroma, 0xff7700);
+        assert!(r == g && g == b);
+
+        assert_eq!(protan.to_string(), "protanopia");
+        assert_eq!(
+            Simulation::new(Deficiency::Deutan, 0.6, Model::Machado).to_string(),
+            "deuteranomaly (60%)"
+        );
+        assert_eq!(
+            Simulation::new(Deficiency::Tritan, 1.0, Model::Machado).to_string(),
+            "tritanopia"
+        );
+    }
+
+    #[test]
+    fn test_daltonize() {
+        let deutan = Simulation::new(Deficiency::Deutan, 1.0, Model::Brettel);
+        let palette = [0xff0000, 0x008000, 0x808080].map(|c| Color::Rgb(Rgb::from_hex(c)));
+        let daltonized = palette.map(|c| deutan.daltonize(c));
+
+        let closest = |colors: &[Color]| {
+            let simulated: Vec<Color> = colors.iter().map(|&c| deutan.simulate(c)).collect();
+            DeltaE::Ciede2000.closest_pair(&simulated).unwrap().2
+        };
+        assert!(closest(&daltonized) > closest(&palette));
+
+        // grays are unchanged
+        let gray = daltonized[2].to_rgb();
+        assert_eq!([gray.r, gray.g, gray.b].map(|n| n.round()), [128.0; 3]);
+    }
+}
@@ -0,0 +1,285 @@
+//! Perceptual color difference metrics (ΔE).
+//!
+//! - http://www2.ece.rochester.edu/~gsharma/ciede2000/
+
+use color_space::{FromRgb, ToRgb};
+
+use super::{space::Lab, Color, Oklab};
+
+/// A color difference metric
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DeltaE {
+    /// CIE76, the euclidean distance in the Lab color space
+    Cie76,
+    /// CIE94 with the weights for graphic arts
+    Cie94,
+    /// CIEDE2000
+    Ciede2000,
+    /// CMC l:c, usually 2:1 (acceptability) or 1:1 (imperceptibility)
+    Cmc { l: f64, c: f64 },
+    /// The euclidean distance in the Oklab color space
+    Ok,
+}
+
+/// A color difference, broken down into lightness, chroma and hue. The
+/// components are signed and weighted according to the metric.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Difference {
+    pub total: f64,
+    pub lightness: f64,
+    pub chroma: f64,
+    pub hue: f64,
+}
+
+impl DeltaE {
+    pub fn name(&self) -> String {
+        match *self {
+            DeltaE::Cie76 => "ΔE76".into(),
+            DeltaE::Cie94 => "ΔE94".into(),
+            DeltaE::Ciede2000 => "ΔE2000".into(),
+            DeltaE::Cmc { l, c } => format!("ΔE CMC {}:{}", l, c),
+            DeltaE::Ok => "ΔEOK".into(),
+        }
+    }
+
+    /// The just noticeable difference, which is used as default tolerance
+    pub fn just_noticeable(&self) -> f64 {
+        match self {
+            DeltaE::Cie76 => 2.3,
+            DeltaE::Ok => 0.02,
+            _ => 1.0,
+        }
+    }
+
+    /// Computes the difference between the colors, where the first color is
+    /// the reference (this matters for CIE94 and CMC, which aren't symmetric)
+    pub fn difference(&self, color1: Color, color2: Color) -> Difference {
+        let lab = |c: Color| Lab::from_rgb(&c.to_rgb());
+        match *self {
+            DeltaE::Cie76 => cie76(lab(color1), lab(color2)),
+            DeltaE::Cie94 => cie94(lab(color1), lab(color2)),
+            DeltaE::Ciede2000 => ciede2000(lab(color1), lab(color2)),
+            DeltaE::Cmc { l, c } => cmc(lab(color1), lab(color2), l, c),
+            DeltaE::Ok => {
+                let ok1 = Oklab::from_rgb(&color1.to_rgb());
+                let ok2 = Oklab::from_rgb(&color2.to_rgb());
+                let (c1, c2) = (ok1.a.hypot(ok1.b), ok2.a.hypot(ok2.b));
+                let delta_h = delta_hue(ok1.a, ok1.b, ok2.a, ok2.b);
+                Difference::new(ok2.l - ok1.l, c2 - c1, delta_h)
+            }
+        }
+    }
+
+    /// Returns the indices of the two colors that are most similar, and their
+    /// difference. Returns `None` if there are fewer than 2 colors.
+    pub fn closest_pair(&self, colors: &[Color]) -> Option<(usize, usize, f64)> {
+        let mut closest: Option<(usize, usize, f64)> = None;
+        for (i, &c1) in colors.iter().enumerate() {
+            for (j, &c2) in colors.iter().enumerate().skip(i + 1) {
+                let d = self.difference(c1, c2).total;
+                if closest.is_none_or(|(_, _, min)| d < min) {
+                    closest = Some((i, j, d));
+                }
+            }
+        }
+        closest
+    }
+}
+
+impl Difference {
+    /// Creates a difference, where the total is the euclidean norm of the
+    /// components
+    fn new(lightness: f64, chroma: f64, hue: f64) -> Self {
+        Difference {
+            total: (lightness * lightness + chroma * chroma + hue * hue).sqrt(),
+            lightness,
+            chroma,
+            hue,
+        }
+    }
+}
+
+/// Computes the CIE76 color difference
+pub fn cie76(lab1: Lab, lab2: Lab) -> Difference {
+    let (c1, c2) = (lab1.a.hypot(lab1.b), lab2.a.hypot(lab2.b));
+    let delta_h = delta_hue(lab1.a, lab1.b, lab2.a, lab2.b);
+    Difference::new(lab2.l - lab1.l, c2 - c1, delta_h)
+}
+
+/// Computes the CIE94 color difference (graphic arts)
+pub fn cie94(lab1: Lab, lab2: Lab) -> Difference {
+    let (c1, c2) = (lab1.a.hypot(lab1.b), lab2.a.hypot(lab2.b));
+    let delta_h = delta_hue(lab1.a, lab1.b, lab2.a, lab2.b);
+    let s_c = 1.0 + 0.045 * c1;
+    let s_h = 1.0 + 0.015 * c1;
+    Difference::new(lab2.l - lab1.l, (c2 - c1) / s_c, delta_h / s_h)
+}
+
+/// Computes the CMC l:c color difference
+pub fn cmc(lab1: Lab, lab2: Lab, l: f64, c: f64) -> Difference {
+    let (c1, c2) = (lab1.a.hypot(lab1.b), lab2.a.hypot(lab2.b));
+    let h1 = hue(lab1.a, lab1.b);
+    let delta_h = delta_hue(lab1.a, lab1.b, lab2.a, lab2.b);
+
+    let s_l = if lab1.l < 16.0 {
+        0.511
+    } else {
+        0.040975 * lab1.l / (1.0 + 0.01765 * lab1.l)
+    };
+    let s_c = 0.0638 * c1 / (1.0 + 0.0131 * c1) + 0.638;
+    let f = (c1.powi(4) / (c1.powi(4) + 1900.0)).sqrt();
+    let t = if (164.0..=345.0).contains(&h1) {
+        0.56 + (0.2 * (h1 + 168.0).to_radians().cos()).abs()
+    } else {
+        0.36 + (0.4 * (h1 + 35.0).to_radians().cos()).abs()
+    };
+    let s_h = s_c * (f * t + 1.0 - f);
+
+    Difference::new(
+        (lab2.l - lab1.l) / (l * s_l),
+        (c2 - c1) / (c * s_c),
+        delta_h / s_h,
+    )
+}
+
+/// Returns the signed hue difference ΔH (not the hue angle difference)
+fn delta_hue(a1: f64, b1: f64, a2: f64, b2: f64) -> f64 {
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let mut delta = hue(a2, b2) - hue(a1, b1);
+    if delta > 180.0 {
+        delta -= 360.0;
+    } else if delta < -180.0 {
+        delta += 360.0;
+    }
+    2.0 * (c1 * c2).sqrt() * (delta / 2.0).to_radians().sin()
+}
+
+/// Computes the CIEDE2000 color difference. A difference of about 1 is barely
+/// perceptible, whereas a difference above 10 means that the colors are
+/// clearly different.
+pub fn ciede2000(lab1: Lab, lab2: Lab) -> Difference {
+    const POW25_7: f64 = 6_103_515_625.0; // 25^7
+
+    let c1 = lab1.a.hypot(lab1.b);
+    let c2 = lab2.a.hypot(lab2.b);
+    let c_bar7 = ((c1 + c2) / 2.0).powi(7);
+    let g = 0.5 * (1.0 - (c_bar7 / (c_bar7 + POW25_7)).sqrt());
+
+    let a1 = (1.0 + g) * lab1.a;
+    let a2 = (1.0 + g) * lab2.a;
+    let c1 = a1.hypot(lab1.b);
+    let c2 = a2.hypot(lab2.b);
+    let h1 = hue(a1, lab1.b);
+    let h2 = hue(a2, lab2.b);
+
+    let delta_l = lab2.l - lab1.l;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else if h2 - h1 < -180.0 {
+        h2 - h1 + 360.0
+    } else {
+        h2 - h1
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_bar = (lab1.l + lab2.l) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let cos = |deg: f64| deg.to_radians().cos();
+    let t =
+        1.0 - 0.17 * cos(h_bar - 30.0) + 0.24 * cos(2.0 * h_bar) + 0.32 * cos(3.0 * h_bar + 6.0)
+            - 0.20 * cos(4.0 * h_bar - 63.0);
+    let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let c_bar7 = c_bar.powi(7);
+    let r_c = 2.0 * (c_bar7 / (c_bar7 + POW25_7)).sqrt();
+    let s_l = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let l = delta_l / s_l;
+    let c = delta_c / s_c;
+    let h = delta_h / s_h;
+    Difference {
+        total: (l * l + c * c + h * h + r_t * c * h).sqrt(),
+        lightness: l,
+        chroma: c,
+        hue: h,
+    }
+}
+
+/// Returns the hue angle in degrees, between 0 and 360
+fn hue(a: f64, b: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ciede2000, DeltaE};
+    use crate::color::{space::Lab, space::Rgb, Color};
+
+    #[test]
+    fn test_ciede2000() {
+        // test data from http://www2.ece.rochester.edu/~gsharma/ciede2000/
+        let data = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+            (
+                [22.7233, 20.0904, -46.694],
+                [23.0331, 14.973, -42.5619],
+                2.0373,
+            ),
+            (
+                [90.9257, -0.5406, -0.9208],
+                [88.6381, -0.8985, -0.7239],
+                1.5381,
+            ),
+        ];
+        for ([l1, a1, b1], [l2, a2, b2], expected) in data {
+            let delta = ciede2000(Lab::new(l1, a1, b1), Lab::new(l2, a2, b2)).total;
+            assert!((delta - expected).abs() < 1e-4, "{} != {}", delta, expected);
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        let orange = Color::Rgb(Rgb::new(255.0, 165.0, 0.0));
+        let dark_orange = Color::Rgb(Rgb::new(255.0, 140.0, 0.0));
+
+        for metric in [
+            DeltaE::Cie76,
+            DeltaE::Cie94,
+            DeltaE::Ciede2000,
+            DeltaE::Cmc { l: 2.0, c: 1.0 },
+            DeltaE::Ok,
+        ] {
+            let same = metric.difference(orange, orange);
+            assert!(same.total.abs() < 1e-9, "{}", metric.name());
+
+            let diff = metric.difference(orange, dark_orange);
+            assert!(diff.total > metric.just_noticeable(), "{}", metric.name());
+            // dark orange is darker
+            assert!(diff.lightness < 0.0, "{}", metric.name());
+        }
+
+        let diff = DeltaE::Cie76.difference(orange, dark_orange);
+        assert!((diff.total - 14.42).abs() < 0.01, "{}", diff.total);
+    }
+}
@@ -1,11 +1,17 @@
-use color_space::ToRgb;
+use color_space::{FromRgb, ToRgb};
 
-use super::{hex, html, Color, ColorSpace};
+use super::{
+    alias,
+    css::CssFunction,
+    difference, hex, html, latex,
+    space::{Lab, Rgb},
+    Color, ColorSpace,
+};
 
 pub const PREFERRED_FORMATS: [&[ColorFormat]; 6] = [
     &[
         ColorFormat::Hex,
-        ColorFormat::Html,
+        ColorFormat::NearestName,
         ColorFormat::Normal(ColorSpace::Rgb),
     ],
     &[
@@ -37,64 +43,377 @@ pub const PREFERRED_FORMATS_SHORT: [ColorFormat; 4] = [
     ColorFormat::Normal(ColorSpace::Hsl),
 ];
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
 pub enum ColorFormat {
     Normal(ColorSpace),
+    #[default]
     Hex,
     Html,
+    /// The name of the closest HTML or user-defined color, e.g.
+    /// `~darkorange (ΔE 2.1)`
+    NearestName,
+    Code(CodeFormat),
+    Css(CssFunction),
+}
+
+/// Color literals in various programming languages and frameworks
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CodeFormat {
+    /// `Color::rgb(1.0, 0.467, 0.0)`
+    Rust,
+    /// `0xff7700u`
+    C,
+    /// `UIColor(red: 1.0, green: 0.467, blue: 0.0, alpha: 1.0)`
+    Swift,
+    /// `Color(0xFFFF7700)` (Jetpack Compose)
+    Kotlin,
+    /// `Color(0xFFFF7700)`
+    Flutter,
+    /// `QColor(255, 119, 0)`
+    Qt,
+    /// `\definecolor{coloFF7700}{HTML}{FF7700}` (LaTeX xcolor)
+    Latex,
+    /// `\definecolor{coloFF7700}{rgb}{1,0.467,0}` (LaTeX xcolor)
+    LatexRgb,
+}
+
+impl CodeFormat {
+    /// Formats the color. Floating-point numbers are rounded to 3 decimal
+    /// digits, unless a different precision is configured.
+    pub fn format(&self, color: Color, num: NumberFormat) -> String {
+        let rgb = color.to_rgb();
+        let hex = hex::rgb_to_u32(rgb);
+        let r = ((hex >> 16) & 0xff) as f64;
+        let g = ((hex >> 8) & 0xff) as f64;
+        let b = (hex & 0xff) as f64;
+
+        match self {
+            CodeFormat::Rust => format!(
+                "Color::rgb({}, {}, {})",
+                float(num.round(r / 255.0, 3)),
+                float(num.round(g / 255.0, 3)),
+                float(num.round(b / 255.0, 3)),
+            ),
+            CodeFormat::C => format!("0x{:06x}u", hex),
+            CodeFormat::Swift => format!(
+                "UIColor(red: {}, green: {}, blue: {}, alpha: 1.0)",
+                float(num.round(r / 255.0, 3)),
+                float(num.round(g / 255.0, 3)),
+                float(num.round(b / 255.0, 3)),
+            ),
+            CodeFormat::Kotlin | CodeFormat::Flutter => format!("Color(0xFF{:06X})", hex),
+            CodeFormat::Qt => format!("QColor({}, {}, {})", r, g, b),
+            CodeFormat::Latex => latex::define_color(color, latex::Model::Html, num),
+            CodeFormat::LatexRgb => latex::define_color(color, latex::Model::Rgb, num),
+        }
+    }
+}
+
+/// Options for formatting the numbers in a color format
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct NumberFormat {
+    /// The number of decimal digits. If `None`, the default of each format is
+    /// used.
+    pub precision: Option<usize>,
+    pub rgb_range: RgbRange,
+    /// Print hex colors in uppercase
+    pub upper_hex: bool,
+    /// Use the 3-digit hex notation where possible, e.g. `#f70`
+    pub short_hex: bool,
+    /// The unit of hue angles. If `None`, hues are printed in degrees without
+    /// a unit.
+    pub angle: Option<AngleUnit>,
+}
+
+/// The range of RGB components
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum RgbRange {
+    /// `0` to `255`
+    #[default]
+    Byte,
+    /// `0.0` to `1.0`
+    Float,
+    /// `0%` to `100%`
+    Percent,
+}
+
+/// The unit of a hue angle
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AngleUnit {
+    Deg,
+    Rad,
+    Grad,
+    Turn,
 }
 
-impl Default for ColorFormat {
-    fn default() -> Self {
-        ColorFormat::Hex
+impl AngleUnit {
+    /// The unit suffix, as it is used in CSS
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            AngleUnit::Deg => "deg",
+            AngleUnit::Rad => "rad",
+            AngleUnit::Grad => "grad",
+            AngleUnit::Turn => "turn",
+        }
+    }
+
+    /// Converts an angle in degrees to this unit
+    pub fn convert(self, deg: f64) -> f64 {
+        match self {
+            AngleUnit::Deg => deg,
+            AngleUnit::Rad => deg.to_radians(),
+            AngleUnit::Grad => deg / 0.9,
+            AngleUnit::Turn => deg / 360.0,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Rounds to the configured precision, or to `default` decimal digits
+    pub(crate) fn round(&self, num: f64, default: usize) -> f64 {
+        let factor = 10f64.powi(self.precision.unwrap_or(default) as i32);
+        // adding 0.0 turns -0.0 into 0.0
+        (num * factor).round() / factor + 0.0
+    }
+
+    /// Multiplies with 100 and rounds
+    pub(crate) fn percent(&self, num: f64) -> f64 {
+        self.round(num * 100.0, 1)
+    }
+
+    /// Formats a hue angle, which is given in degrees. If `always_unit` is
+    /// true, `deg` is appended when no unit is configured.
+    pub(crate) fn angle(&self, deg: f64, always_unit: bool) -> String {
+        match self.angle {
+            None if always_unit => format!("{}deg", self.round(deg, 1)),
+            None => self.round(deg, 1).to_string(),
+            Some(unit) => {
+                let default = match unit {
+                    AngleUnit::Deg | AngleUnit::Grad => 1,
+                    AngleUnit::Rad | AngleUnit::Turn => 3,
+                };
+                format!(
+                    "{}{}",
+                    self.round(unit.convert(deg), default),
+                    unit.suffix()
+                )
+            }
+        }
+    }
+
+    /// Formats the components of an RGB color, which are between 0 and 255
+    pub(crate) fn rgb(&self, rgb: [f64; 3]) -> [String; 3] {
+        rgb.map(|n| match self.rgb_range {
+            RgbRange::Byte => self.round(n, 1).to_string(),
+            RgbRange::Float => self.round(n / 255.0, 3).to_string(),
+            RgbRange::Percent => format!("{}%", self.round(n / 2.55, 1)),
+        })
+    }
+
+    /// Formats a color in hex notation, e.g. `#ff7700`
+    pub(crate) fn hex(&self, color: Color) -> String {
+        let hex = hex::rgb_to_u32(color.to_rgb());
+        let is_short = [0, 8, 16]
+            .iter()
+            .all(|&s| (hex >> s) & 0xf == (hex >> (s + 4)) & 0xf);
+
+        let s = if self.short_hex && is_short {
+            let [r, g, b] = [16, 8, 0].map(|s| (hex >> s) & 0xf);
+            format!("#{:x}{:x}{:x}", r, g, b)
+        } else {
+            format!("#{:06x}", hex)
+        };
+        if self.upper_hex {
+            s.to_uppercase()
+        } else {
+            s
+        }
     }
 }
 
 impl ColorFormat {
     pub fn format(&self, color: Color) -> Option<String> {
+        self.format_with(color, NumberFormat::default())
+    }
+
+    /// Formats the color, using the given options for formatting numbers
+    pub fn format_with(&self, color: Color, num: NumberFormat) -> Option<String> {
         Some(match *self {
             ColorFormat::Normal(space) => {
                 let (_, parts) = color.to_color_space(space).divide();
-                let a = *parts.get(0).unwrap_or(&0.0);
+                let a = *parts.first().unwrap_or(&0.0);
                 let b = *parts.get(1).unwrap_or(&0.0);
                 let c = *parts.get(2).unwrap_or(&0.0);
                 let d = *parts.get(3).unwrap_or(&0.0);
 
+                let r = |n| num.round(n, 1);
+                let r3 = |n| num.round(n, 3);
+                let p = |n| num.percent(n);
+                let h = |n| num.angle(n, false);
+
                 match space {
-                    ColorSpace::Rgb => format!("rgb({}, {}, {})", r(a), r(b), r(c)),
+                    ColorSpace::Rgb => {
+                        let [a, b, c] = num.rgb([a, b, c]);
+                        format!("rgb({}, {}, {})", a, b, c)
+                    }
                     ColorSpace::Cmy => format!("cmy({}%, {}%, {}%)", p(a), p(b), p(c)),
                     ColorSpace::Cmyk => format!("cmyk({}%, {}%, {}%, {}%)", p(a), p(b), p(c), p(d)),
-                    ColorSpace::Hsv => format!("hsv({}, {}%, {}%)", r(a), p(b), p(c)),
-                    ColorSpace::Hsl => format!("hsl({}, {}%, {}%)", r(a), p(b), p(c)),
-                    ColorSpace::Lch => format!("lch({}, {}, {})", r(a), r(b), r(c)),
+                    ColorSpace::Hsv => format!("hsv({}, {}%, {}%)", h(a), p(b), p(c)),
+                    ColorSpace::Hsl => format!("hsl({}, {}%, {}%)", h(a), p(b), p(c)),
+                    ColorSpace::Lch => format!("lch({}, {}, {})", r(a), r(b), h(c)),
                     ColorSpace::Luv => format!("luv({}, {}, {})", r(a), r(b), r(c)),
                     ColorSpace::Lab => format!("lab({}, {}, {})", r(a), r(b), r(c)),
                     ColorSpace::HunterLab => format!("hunterlab({}, {}, {})", r(a), r(b), r(c)),
                     ColorSpace::Xyz => format!("xyz({}, {}, {})", r(a), r(b), r(c)),
                     ColorSpace::Yxy => format!("yxy({}, {}, {})", r(a), r(b), r(c)),
                     ColorSpace::Gray => format!("gry({}%)", p(a)),
+                    ColorSpace::Oklab => format!("oklab({}%, {}, {})", p(a), r3(b), r3(c)),
+                    ColorSpace::Oklch => format!("oklch({}%, {}, {})", p(a), r3(b), h(c)),
                 }
             }
-            ColorFormat::Hex => format!("#{:06x}", hex::rgb_to_u32(color.to_rgb())),
+            ColorFormat::Hex => num.hex(color),
             ColorFormat::Html => {
-                let name = html::get_name(color.to_rgb())?;
+                let rgb = color.to_rgb();
+                let name = alias::get_name(rgb).or_else(|| html::get_name(rgb))?;
                 name.to_string()
             }
+            ColorFormat::NearestName => {
+                let (name, delta_e) = nearest_name(color);
+                if num.round(delta_e, 1) == 0.0 {
+                    name.to_string()
+                } else {
+                    format!("~{} (ΔE {})", name, num.round(delta_e, 1))
+                }
+            }
+            ColorFormat::Code(code) => code.format(color, num),
+            ColorFormat::Css(function) => function.format(color, num)?,
         })
     }
 
+    /// Returns the color model if this is a LaTeX color definition, which
+    /// contains the name of the color
+    pub fn latex_model(&self) -> Option<latex::Model> {
+        match self {
+            ColorFormat::Code(CodeFormat::Latex) => Some(latex::Model::Html),
+            ColorFormat::Code(CodeFormat::LatexRgb) => Some(latex::Model::Rgb),
+            _ => None,
+        }
+    }
+
     pub fn format_or_hex(&self, color: Color) -> String {
-        self.format(color)
-            .unwrap_or_else(|| ColorFormat::Hex.format(color).unwrap())
+        self.format_or_hex_with(color, NumberFormat::default())
     }
+
+    pub fn format_or_hex_with(&self, color: Color, num: NumberFormat) -> String {
+        self.format_with(color, num)
+            .unwrap_or_else(|| num.hex(color))
+    }
+}
+
+/// Finds the HTML color or user-defined color that is perceptually closest to
+/// the given color, and returns its name and the CIEDE2000 difference
+fn nearest_name(color: Color) -> (&'static str, f64) {
+    let lab = Lab::from_rgb(&color.to_rgb());
+    let aliases = alias::all()
+        .iter()
+        .map(|(name, c)| (name.as_str(), c.to_rgb()));
+    let html = html::HTML_COLOR_NAMES
+        .iter()
+        .map(|&(name, hex)| (name, Rgb::from_hex(hex)));
+
+    aliases
+        .chain(html)
+        .map(|(name, rgb)| (name, difference::ciede2000(lab, Lab::from_rgb(&rgb)).total))
+        .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+        .expect("there are HTML colors")
 }
 
-/// Round to 1 decimal digit
-fn r(num: f64) -> f64 {
-    (num * 10.0).round() / 10.0
+/// Formats the number so that it always contains a decimal point, as required
+/// for floating-point literals in many languages
+fn float(num: f64) -> String {
+    let s = num.to_string();
+    if s.contains('.') {
+        s
+    } else {
+        s + ".0"
+    }
 }
 
-/// Multiply with 100 and round to 1 decimal digit
-fn p(num: f64) -> f64 {
-    (num * 1000.0).round() / 10.0
+#[cfg(test)]
+mod tests {
+    use super::{AngleUnit, CodeFormat, ColorFormat, NumberFormat, RgbRange};
+    use crate::color::{space::Rgb, Color, ColorSpace};
+
+    #[test]
+    fn test_code_formats() {
+        let orange = Color::Rgb(Rgb::new(255.0, 119.0, 0.0));
+        let format = |code| ColorFormat::Code(code).format_or_hex(orange);
+
+        assert_eq!(format(CodeFormat::Rust), "Color::rgb(1.0, 0.467, 0.0)");
+        assert_eq!(format(CodeFormat::C), "0xff7700u");
+        assert_eq!(
+            format(CodeFormat::Swift),
+            "UIColor(red: 1.0, green: 0.467, blue: 0.0, alpha: 1.0)"
+        );
+        assert_eq!(format(CodeFormat::Flutter), "Color(0xFFFF7700)");
+        assert_eq!(format(CodeFormat::Qt), "QColor(255, 119, 0)");
+
+        let num = NumberFormat {
+            precision: Some(1),
+            ..Default::default()
+        };
+        let format = |code| ColorFormat::Code(code).format_or_hex_with(orange, num);
+        assert_eq!(format(CodeFormat::Rust), "Color::rgb(1.0, 0.5, 0.0)");
+        assert_eq!(
+            format(CodeFormat::LatexRgb),
+            r"\definecolor{coloFF7700}{rgb}{1,0.5,0}"
+        );
+    }
+
+    #[test]
+    fn test_number_format() {
+        let orange = Color::Rgb(Rgb::new(255.0, 119.0, 0.0));
+        let format = |format, num| ColorFormat::format_or_hex_with(&format, orange, num);
+        let rgb = ColorFormat::Normal(ColorSpace::Rgb);
+        let hsl = ColorFormat::Normal(ColorSpace::Hsl);
+
+        let num = NumberFormat {
+            rgb_range: RgbRange::Float,
+            ..Default::default()
+        };
+        assert_eq!(format(rgb, num), "rgb(1, 0.467, 0)");
+        let num = NumberFormat {
+            rgb_range: RgbRange::Percent,
+            precision: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(format(rgb, num), "rgb(100%, 47%, 0%)");
+
+        let num = NumberFormat {
+            angle: Some(AngleUnit::Turn),
+            ..Default::default()
+        };
+        assert_eq!(format(hsl, num), "hsl(0.078turn, 100%, 50%)");
+
+        let num = NumberFormat {
+            upper_hex: true,
+            short_hex: true,
+            ..Default::default()
+        };
+        assert_eq!(format(ColorFormat::Hex, num), "#F70");
+        let white = Color::Rgb(Rgb::new(255.0, 254.0, 255.0));
+        assert_eq!(ColorFormat::Hex.format_or_hex_with(white, num), "#FFFEFF");
+    }
+
+    #[test]
+    fn test_nearest_name() {
+        let orange = Color::Rgb(Rgb::new(255.0, 165.0, 0.0));
+        let almost_orange = Color::Rgb(Rgb::new(255.0, 119.0, 0.0));
+
+        assert_eq!(ColorFormat::NearestName.format_or_hex(orange), "orange");
+        assert_eq!(
+            ColorFormat::NearestName.format_or_hex(almost_orange),
+            "~darkorange (ΔE 6.7)"
+        );
+    }
 }
@@ -0,0 +1,67 @@
+//! Mapping colors into the sRGB gamut.
+
+use color_space::ToRgb;
+
+use super::{space::Rgb, Color, ColorSpace};
+
+/// Returns whether the color can be displayed in sRGB, allowing for rounding
+/// errors
+pub fn in_gamut(color: Color) -> bool {
+    let rgb = color.to_rgb();
+    [rgb.r, rgb.g, rgb.b]
+        .iter()
+        .all(|&n| (-0.5..=255.5).contains(&n))
+}
+
+/// Maps the color into the sRGB gamut by reducing its chroma in `color_space`
+/// (`Lch` or `Oklch`) while keeping the lightness and hue. The result is
+/// clamped, so it is always an RGB color in the gamut.
+pub fn map_to_gamut(color: Color, color_space: ColorSpace) -> Color {
+    if in_gamut(color) {
+        return clamp(color.to_rgb());
+    }
+    if !matches!(color_space, ColorSpace::Lch | ColorSpace::Oklch) {
+        return clamp(color.to_rgb());
+    }
+
+    let (_, lch) = color.to_color_space(color_space).divide();
+    let with_chroma = |c: f64| Color::new_unchecked(color_space, &[lch[0], c, lch[2]]);
+
+    let (mut low, mut high) = (0.0, lch[1]);
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if in_gamut(with_chroma(mid)) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    clamp(with_chroma(low).to_rgb())
+}
+
+fn clamp(rgb: Rgb) -> Color {
+    Color::Rgb(Rgb::new(
+        rgb.r.clamp(0.0, 255.0),
+        rgb.g.clamp(0.0, 255.0),
+        rgb.b.clamp(0.0, 255.0),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{in_gamut, map_to_gamut};
+    use crate::color::{Color, ColorSpace};
+
+    #[test]
+    fn test_map_to_gamut() {
+        let vivid = Color::new_unchecked(ColorSpace::Lch, &[60.0, 150.0, 140.0]);
+        assert!(!in_gamut(vivid));
+
+        let mapped = map_to_gamut(vivid, ColorSpace::Lch);
+        assert!(in_gamut(mapped));
+        let (_, lch) = mapped.to_color_space(ColorSpace::Lch).divide();
+        assert!((lch[0] - 60.0).abs() < 0.5, "{:?}", lch);
+        assert!((lch[2] - 140.0).abs() < 1.0, "{:?}", lch);
+        assert!(lch[1] < 150.0);
+    }
+}
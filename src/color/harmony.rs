@@ -0,0 +1,110 @@
+//! Color harmonies, i.e. sets of colors with evenly distributed hues.
+
+use super::{
+    adjust::{self, Adjustment},
+    Color, ColorSpace,
+};
+
+/// A color scheme based on the color wheel
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Scheme {
+    /// The base color and the opposite color
+    Complementary,
+    /// The base color and the two colors adjacent to its complement
+    SplitComplementary,
+    /// Three colors evenly spaced around the color wheel
+    Triadic,
+    /// Two pairs of complementary colors, forming a rectangle
+    Tetradic,
+    /// Four colors evenly spaced around the color wheel
+    Square,
+    /// The base color and its neighbors on both sides
+    Analogous,
+}
+
+impl Scheme {
+    pub const ALL: [Scheme; 6] = [
+        Scheme::Complementary,
+        Scheme::SplitComplementary,
+        Scheme::Triadic,
+        Scheme::Tetradic,
+        Scheme::Square,
+        Scheme::Analogous,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Scheme::Complementary => "complementary",
+            Scheme::SplitComplementary => "split-complementary",
+            Scheme::Triadic => "triadic",
+            Scheme::Tetradic => "tetradic",
+            Scheme::Square => "square",
+            Scheme::Analogous => "analogous",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Scheme::ALL
+            .iter()
+            .copied()
+            .find(|s| s.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the hue rotations of the colors in degrees. `angle` is the
+    /// distance between neighbors in analogous schemes, and the distance from
+    /// the complement in split-complementary and tetradic schemes.
+    pub fn rotations(self, angle: f64) -> Vec<f64> {
+        match self {
+            Scheme::Complementary => vec![0.0, 180.0],
+            Scheme::SplitComplementary => vec![0.0, 180.0 - angle, 180.0 + angle],
+            Scheme::Triadic => vec![0.0, 120.0, 240.0],
+            Scheme::Tetradic => vec![0.0, angle, 180.0, 180.0 + angle],
+            Scheme::Square => vec![0.0, 90.0, 180.0, 270.0],
+            Scheme::Analogous => vec![-angle, 0.0, angle],
+        }
+    }
+
+    /// Returns the colors of the scheme, by rotating the hue of the base color
+    /// in `color_space` (`Hsl`, `Lch` or `Oklch`)
+    pub fn colors(self, base: Color, angle: f64, color_space: ColorSpace) -> Vec<Color> {
+        self.rotations(angle)
+            .into_iter()
+            .map(|deg| adjust::adjust(base, &[Adjustment::Rotate(deg)], color_space))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scheme;
+    use crate::color::{space::Rgb, Color, ColorSpace};
+    use color_space::ToRgb;
+
+    #[test]
+    fn test_harmony() {
+        let red = Color::Rgb(Rgb::from_hex(0xff0000));
+        let hex = |colors: Vec<Color>| -> Vec<[u8; 3]> {
+            colors
+                .iter()
+                .map(|c| {
+                    let rgb = c.to_rgb();
+                    [rgb.r, rgb.g, rgb.b].map(|n| n.round() as u8)
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            hex(Scheme::Triadic.colors(red, 30.0, ColorSpace::Hsl)),
+            [[255, 0, 0], [0, 255, 0], [0, 0, 255]]
+        );
+        assert_eq!(
+            hex(Scheme::SplitComplementary.colors(red, 30.0, ColorSpace::Hsl)),
+            [[255, 0, 0], [0, 255, 128], [0, 128, 255]]
+        );
+        assert_eq!(
+            Scheme::from_name("Split-Complementary"),
+            Some(Scheme::SplitComplementary)
+        );
+        assert_eq!(Scheme::Analogous.rotations(20.0), [-20.0, 0.0, 20.0]);
+    }
+}
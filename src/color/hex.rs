@@ -41,7 +41,7 @@ pub fn parse(input: &str) -> Result<space::Rgb, ParseHexError> {
     if color.is_empty() {
         return Err(ParseHexError::NoDigits);
     }
-    if color.len() % 3 != 0 {
+    if !color.len().is_multiple_of(3) {
         return Err(ParseHexError::DigitsNotDivisibleBy3 {
             string: input.into(),
             got: color.len() as u32,
@@ -91,9 +91,9 @@ fn scale_down(r: f64, g: f64, b: f64, len: usize) -> space::Rgb {
 
 /// Converts an RGB color to hexadecimal notation
 pub fn rgb_to_u32(rgb: space::Rgb) -> u32 {
-    let r = rgb.r.min(255.0).max(0.0).round() as u32;
-    let g = rgb.g.min(255.0).max(0.0).round() as u32;
-    let b = rgb.b.min(255.0).max(0.0).round() as u32;
+    let r = rgb.r.clamp(0.0, 255.0).round() as u32;
+    let g = rgb.g.clamp(0.0, 255.0).round() as u32;
+    let b = rgb.b.clamp(0.0, 255.0).round() as u32;
     (r << 16) + (g << 8) + b
 }
 
@@ -0,0 +1,154 @@
+//! Color definitions for LaTeX, using the `xcolor`, TikZ and `pgfplots`
+//! packages.
+
+use std::fmt::Write;
+
+use color_space::ToRgb;
+
+use super::{alias, format::NumberFormat, hex, html, Color};
+
+/// The color model of an xcolor definition
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Model {
+    /// `\definecolor{orange}{HTML}{FFA500}`
+    Html,
+    /// `\definecolor{orange}{rgb}{1,0.647,0}`
+    Rgb,
+}
+
+/// Returns an xcolor definition of the color. The name is the HTML or
+/// user-defined name of the color if it has one, otherwise it is derived from
+/// the hex value, e.g. `coloFF7700`.
+pub fn define_color(color: Color, model: Model, num: NumberFormat) -> String {
+    let rgb = color.to_rgb();
+    let name = match alias::get_name(rgb).or_else(|| html::get_name(rgb)) {
+        Some(name) => name.to_string(),
+        None => format!("colo{:06X}", hex::rgb_to_u32(rgb)),
+    };
+    define_named_color(&name, color, model, num)
+}
+
+/// Returns an xcolor definition of the color with the given name. Characters
+/// that aren't allowed in color names are removed.
+pub fn define_named_color(name: &str, color: Color, model: Model, num: NumberFormat) -> String {
+    format!(
+        "\\definecolor{{{}}}{{{}}}",
+        color_name(name),
+        value(color, model, num)
+    )
+}
+
+/// Returns the model and the color components, e.g. `HTML}{FFA500`. RGB
+/// components are rounded to 3 decimal digits by default.
+fn value(color: Color, model: Model, num: NumberFormat) -> String {
+    let hex = hex::rgb_to_u32(color.to_rgb());
+    match model {
+        Model::Html => format!("HTML}}{{{:06X}", hex),
+        Model::Rgb => {
+            let [r, g, b] = bytes(hex).map(|n| num.round(n as f64 / 255.0, 3));
+            format!("rgb}}{{{},{},{}", r, g, b)
+        }
+    }
+}
+
+/// A gradient definition
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Colormap {
+    /// A TikZ shading
+    Tikz,
+    /// A pgfplots colormap
+    Pgfplots,
+}
+
+impl Colormap {
+    pub fn format(&self, name: &str, colors: &[Color]) -> String {
+        match self {
+            Colormap::Tikz => tikz_shading(name, colors),
+            Colormap::Pgfplots => pgfplots_colormap(name, colors),
+        }
+    }
+}
+
+/// Returns a TikZ shading definition with equally spaced colors, which can be
+/// used with `\shade[shading=name]`
+pub fn tikz_shading(name: &str, colors: &[Color]) -> String {
+    let mut stops = String::new();
+    for (i, &color) in colors.iter().enumerate() {
+        let position = i as f64 * 100.0 / (colors.len().max(2) - 1) as f64;
+        let [r, g, b] = bytes(hex::rgb_to_u32(color.to_rgb())).map(|n| round(n as f64 / 255.0));
+        if i > 0 {
+            stops.push_str("; ");
+        }
+        write!(stops, "rgb({}bp)=({},{},{})", round(position), r, g, b).unwrap();
+    }
+    format!(
+        "\\pgfdeclarehorizontalshading{{{name}}}{{100bp}}{{{stops}}}\n\
+        % usage: \\shade[shading={name}] (0,0) rectangle (4,1);",
+        name = color_name(name),
+        stops = stops,
+    )
+}
+
+/// Returns a pgfplots colormap definition with equally spaced colors
+pub fn pgfplots_colormap(name: &str, colors: &[Color]) -> String {
+    let mut stops = String::new();
+    for (i, &color) in colors.iter().enumerate() {
+        let [r, g, b] = bytes(hex::rgb_to_u32(color.to_rgb()));
+        if i > 0 {
+            stops.push(' ');
+        }
+        write!(stops, "rgb255=({},{},{})", r, g, b).unwrap();
+    }
+    format!(
+        "\\pgfplotsset{{colormap={{{}}}{{{}}}}}",
+        color_name(name),
+        stops
+    )
+}
+
+/// Removes characters that aren't allowed in color names
+fn color_name(name: &str) -> String {
+    name.chars().filter(char::is_ascii_alphanumeric).collect()
+}
+
+fn bytes(hex: u32) -> [u32; 3] {
+    [(hex >> 16) & 0xff, (hex >> 8) & 0xff, hex & 0xff]
+}
+
+/// Round to 3 decimal digits
+fn round(num: f64) -> f64 {
+    (num * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{define_color, define_named_color, pgfplots_colormap, tikz_shading, Model};
+    use crate::color::{format::NumberFormat, space::Rgb, Color};
+
+    #[test]
+    fn test_latex() {
+        let orange = Color::Rgb(Rgb::new(255.0, 165.0, 0.0));
+        let custom = Color::Rgb(Rgb::new(255.0, 119.0, 0.0));
+        let num = NumberFormat::default();
+
+        assert_eq!(
+            define_color(orange, Model::Html, num),
+            r"\definecolor{orange}{HTML}{FFA500}"
+        );
+        assert_eq!(
+            define_color(custom, Model::Rgb, num),
+            r"\definecolor{coloFF7700}{rgb}{1,0.467,0}"
+        );
+        assert_eq!(
+            define_named_color("brand.primary", custom, Model::Html, num),
+            r"\definecolor{brandprimary}{HTML}{FF7700}"
+        );
+        assert_eq!(
+            pgfplots_colormap("my-map", &[orange, custom]),
+            r"\pgfplotsset{colormap={mymap}{rgb255=(255,165,0) rgb255=(255,119,0)}}"
+        );
+        assert!(tikz_shading("colo", &[orange, custom]).starts_with(
+            r"\pgfdeclarehorizontalshading{colo}{100bp}{rgb(0bp)=(1,0.647,0); rgb(100bp)=(1,0.467,0)}"
+        ));
+    }
+}
@@ -4,20 +4,35 @@ use std::fmt;
 
 use space::*;
 
-pub(crate) use self::contrast::contrast;
-pub(crate) use format::ColorFormat;
-pub(crate) use parse::{parse, ParseError};
+pub(crate) use self::alias::load as load_aliases;
+pub(crate) use self::contrast::{contrast, Algorithm};
+pub(crate) use css::CssFunction;
+pub(crate) use format::{CodeFormat, ColorFormat};
+pub(crate) use parse::{parse, parse_literal, ParseError};
 pub(crate) use space::ColorSpace;
 
-mod contrast;
+pub(crate) mod contrast;
 mod convert;
 mod gray;
+mod oklab;
 mod parse;
 
+pub mod adjust;
+pub mod alias;
+pub mod css;
+pub mod cvd;
+pub mod difference;
 pub mod format;
+pub mod gamut;
+pub mod harmony;
 pub mod hex;
 pub mod html;
+pub mod latex;
+pub mod scale;
+pub mod scan;
 pub mod space;
+pub mod stylesheet;
+pub mod template;
 
 /// A color enum that unifies the color types specific to a color space.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -34,6 +49,8 @@ pub enum Color {
     Xyz(Xyz),
     Yxy(Yxy),
     Gray(Gray),
+    Oklab(Oklab),
+    Oklch(Oklch),
 }
 
 impl Color {
@@ -61,6 +78,8 @@ impl Color {
             Color::Xyz(_) => ColorSpace::Xyz,
             Color::Yxy(_) => ColorSpace::Yxy,
             Color::Gray(_) => ColorSpace::Gray,
+            Color::Oklab(_) => ColorSpace::Oklab,
+            Color::Oklch(_) => ColorSpace::Oklch,
         }
     }
 
@@ -84,6 +103,8 @@ impl Color {
             Color::Xyz(color) => (ColorSpace::Xyz, vec![color.x, color.y, color.z]),
             Color::Yxy(color) => (ColorSpace::Yxy, vec![color.y1, color.x, color.y2]),
             Color::Gray(color) => (ColorSpace::Gray, vec![color.l]),
+            Color::Oklab(color) => (ColorSpace::Oklab, vec![color.l, color.a, color.b]),
+            Color::Oklch(color) => (ColorSpace::Oklch, vec![color.l, color.c, color.h]),
         }
     }
 
@@ -128,6 +149,8 @@ impl Color {
             ColorSpace::Xyz => Color::Xyz(color.into()),
             ColorSpace::Yxy => Color::Yxy(color.into()),
             ColorSpace::Gray => Color::Gray(color.into()),
+            ColorSpace::Oklab => Color::Oklab(color.into()),
+            ColorSpace::Oklch => Color::Oklch(color.into()),
         }
     }
 
@@ -193,9 +216,9 @@ impl Color {
 
     fn clamp_rgb(rgb: Rgb) -> Rgb {
         Rgb {
-            r: rgb.r.min(255.0).max(0.0),
-            g: rgb.g.min(255.0).max(0.0),
-            b: rgb.b.min(255.0).max(0.0),
+            r: rgb.r.clamp(0.0, 255.0),
+            g: rgb.g.clamp(0.0, 255.0),
+            b: rgb.b.clamp(0.0, 255.0),
         }
     }
 }
@@ -236,6 +259,8 @@ impl ToRgb for Color {
             Color::Xyz(color) => color.to_rgb(),
             Color::Yxy(color) => color.to_rgb(),
             Color::Gray(color) => color.to_rgb(),
+            Color::Oklab(color) => color.to_rgb(),
+            Color::Oklch(color) => color.to_rgb(),
         }
     }
 }
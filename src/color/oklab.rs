@@ -0,0 +1,192 @@
+//! The Oklab color space and its polar form Oklch.
+//!
+//! - https://bottosson.github.io/posts/oklab/
+
+use super::space::*;
+use color_space::{FromRgb, ToRgb};
+
+/// An Oklab color. `l` is between 0 and 1, `a` and `b` are usually between
+/// -0.4 and 0.4.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Oklab {
+    pub fn new(l: f64, a: f64, b: f64) -> Self {
+        Self { l, a, b }
+    }
+}
+
+/// An Oklch color. `l` is between 0 and 1, `c` is usually between 0 and 0.4
+/// and `h` is the hue angle in degrees.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+impl Oklch {
+    pub fn new(l: f64, c: f64, h: f64) -> Self {
+        Self { l, c, h }
+    }
+}
+
+/// Converts an sRGB component (between 0 and 1) to linear light. Negative
+/// values are supported to allow out-of-gamut colors.
+pub(crate) fn srgb_to_linear(n: f64) -> f64 {
+    let abs = n.abs();
+    let linear = if abs <= 0.04045 {
+        abs / 12.92
+    } else {
+        ((abs + 0.055) / 1.055).powf(2.4)
+    };
+    linear.copysign(n)
+}
+
+/// Converts a linear light component to sRGB (between 0 and 1)
+pub(crate) fn linear_to_srgb(n: f64) -> f64 {
+    let abs = n.abs();
+    let srgb = if abs <= 0.0031308 {
+        abs * 12.92
+    } else {
+        1.055 * abs.powf(1.0 / 2.4) - 0.055
+    };
+    srgb.copysign(n)
+}
+
+impl FromRgb for Oklab {
+    fn from_rgb(rgb: &Rgb) -> Self {
+        let r = srgb_to_linear(rgb.r / 255.0);
+        let g = srgb_to_linear(rgb.g / 255.0);
+        let b = srgb_to_linear(rgb.b / 255.0);
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Oklab::new(
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        )
+    }
+}
+
+impl ToRgb for Oklab {
+    fn to_rgb(&self) -> Rgb {
+        let l = (self.l + 0.3963377774 * self.a + 0.2158037573 * self.b).powi(3);
+        let m = (self.l - 0.1055613458 * self.a - 0.0638541728 * self.b).powi(3);
+        let s = (self.l - 0.0894841775 * self.a - 1.2914855480 * self.b).powi(3);
+
+        let r = 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s;
+        let g = -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s;
+        let b = -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s;
+
+        Rgb::new(
+            linear_to_srgb(r) * 255.0,
+            linear_to_srgb(g) * 255.0,
+            linear_to_srgb(b) * 255.0,
+        )
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(lch: Oklch) -> Self {
+        let h = lch.h.to_radians();
+        Oklab::new(lch.l, lch.c * h.cos(), lch.c * h.sin())
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(lab: Oklab) -> Self {
+        let c = (lab.a * lab.a + lab.b * lab.b).sqrt();
+        // the hue is meaningless for achromatic colors
+        let h = if c < 1e-6 {
+            0.0
+        } else {
+            lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0)
+        };
+        Oklch::new(lab.l, c, h)
+    }
+}
+
+impl FromRgb for Oklch {
+    fn from_rgb(rgb: &Rgb) -> Self {
+        Oklab::from_rgb(rgb).into()
+    }
+}
+
+impl ToRgb for Oklch {
+    fn to_rgb(&self) -> Rgb {
+        Oklab::from(*self).to_rgb()
+    }
+}
+
+macro_rules! impl_froms {
+    ($t:ty) => {
+        impl From<$t> for Oklab {
+            fn from(c: $t) -> Self {
+                Oklab::from_rgb(&c.to_rgb())
+            }
+        }
+
+        impl From<Oklab> for $t {
+            fn from(c: Oklab) -> Self {
+                <$t>::from_rgb(&c.to_rgb())
+            }
+        }
+
+        impl From<$t> for Oklch {
+            fn from(c: $t) -> Self {
+                Oklch::from_rgb(&c.to_rgb())
+            }
+        }
+
+        impl From<Oklch> for $t {
+            fn from(c: Oklch) -> Self {
+                <$t>::from_rgb(&c.to_rgb())
+            }
+        }
+    };
+}
+
+impl_froms!(Rgb);
+impl_froms!(Cmy);
+impl_froms!(Cmyk);
+impl_froms!(Hsv);
+impl_froms!(Hsl);
+impl_froms!(Lch);
+impl_froms!(Luv);
+impl_froms!(Lab);
+impl_froms!(HunterLab);
+impl_froms!(Xyz);
+impl_froms!(Yxy);
+impl_froms!(Gray);
+
+#[cfg(test)]
+mod tests {
+    use super::{Oklab, Oklch};
+    use crate::color::space::Rgb;
+    use color_space::{FromRgb, ToRgb};
+
+    #[test]
+    fn test_oklab() {
+        let white = Oklab::from_rgb(&Rgb::new(255.0, 255.0, 255.0));
+        assert!((white.l - 1.0).abs() < 1e-4);
+        assert!(white.a.abs() < 1e-4 && white.b.abs() < 1e-4);
+
+        let red = Oklch::from_rgb(&Rgb::new(255.0, 0.0, 0.0));
+        assert!((red.l - 0.628).abs() < 1e-3);
+        assert!((red.c - 0.2577).abs() < 1e-3);
+        assert!((red.h - 29.23).abs() < 1e-2);
+
+        let rgb = Oklab::from_rgb(&Rgb::new(12.0, 200.0, 77.0)).to_rgb();
+        assert!((rgb.r - 12.0).abs() < 1e-3);
+        assert!((rgb.g - 200.0).abs() < 1e-3);
+        assert!((rgb.b - 77.0).abs() < 1e-3);
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::anyhow;
 use std::{cmp::Ordering, num::ParseFloatError};
 use thiserror::Error;
 
-use super::{hex, html, Color, ColorFormat, ColorSpace};
+use super::{alias, hex, html, Color, ColorFormat, ColorSpace};
 use crate::{
     terminal::{stdin, ColorPicker},
     State,
@@ -51,6 +51,12 @@ pub enum ParseError {
     #[error("Unknown color {got:?}, did you mean {suggestion:?}?")]
     Misspelled { got: String, suggestion: String },
 
+    #[error("Unknown palette {name:?}")]
+    UnknownPalette { name: String },
+
+    #[error("Expected a literal color, got {got:?}")]
+    NotLiteral { got: String },
+
     #[error("This value in the {cs:?} color space can't be randomly generated")]
     UnsupportedRand { cs: ColorSpace },
 
@@ -60,7 +66,22 @@ pub enum ParseError {
 
 /// Parses a string that can contain an arbitrary number of colors in different
 /// formats
-pub fn parse(mut input: &str, state: State) -> Result<Vec<(Color, ColorFormat)>, ParseError> {
+pub fn parse(input: &str, state: State) -> Result<Vec<(Color, ColorFormat)>, ParseError> {
+    parse_colors(input, state, false)
+}
+
+/// Like `parse`, but only accepts literal colors (hex colors, HTML color names
+/// and color components), not `rand`, `pick`, stdin (`-`) or user-defined
+/// colors
+pub fn parse_literal(input: &str, state: State) -> Result<Vec<(Color, ColorFormat)>, ParseError> {
+    parse_colors(input, state, true)
+}
+
+fn parse_colors(
+    mut input: &str,
+    state: State,
+    literal: bool,
+) -> Result<Vec<(Color, ColorFormat)>, ParseError> {
     let mut output = Vec::new();
     loop {
         let input_i = input.trim_start();
@@ -83,7 +104,13 @@ pub fn parse(mut input: &str, state: State) -> Result<Vec<(Color, ColorFormat)>,
                 input_i = input_i.trim_start();
                 let (n, input_ii) = parse_number(input_i)?
                     .map(Ok)
-                    .or_else(|| parse_rand_component(input_i, cs, i).transpose())
+                    .or_else(|| {
+                        if literal {
+                            None
+                        } else {
+                            parse_rand_component(input_i, cs, i).transpose()
+                        }
+                    })
                     .transpose()?
                     .ok_or_else(|| MissingFloat {
                         got: input_i.into(),
@@ -94,9 +121,13 @@ pub fn parse(mut input: &str, state: State) -> Result<Vec<(Color, ColorFormat)>,
             }
 
             let nums = &nums[0..expected];
-            let color: Color = Color::new(cs, nums).map_err(|err| err)?;
+            let color: Color = Color::new(cs, nums)?;
             output.push((color, ColorFormat::Normal(cs)));
             input_i = input_i.trim_start();
+        } else if literal && (input_i.starts_with('-') || input_i.starts_with('@')) {
+            return Err(NotLiteral {
+                got: input_i.split_whitespace().next().unwrap_or_default().into(),
+            });
         } else if input_i.starts_with("- ") || input_i.starts_with("-,") {
             input_i = input_i[2..].trim_start();
 
@@ -106,12 +137,29 @@ pub fn parse(mut input: &str, state: State) -> Result<Vec<(Color, ColorFormat)>,
                 return Err(anyhow!("Expected 1 color, got {}", colors.len()).into());
             }
             output.push(colors[0]);
+        } else if let Some(rest) = input_i.strip_prefix('@') {
+            let (name, input_ii) = take_name(rest).ok_or_else(|| ParseError::ExpectedWord {
+                string: input_i.into(),
+            })?;
+
+            let colors = alias::get_palette(name);
+            if colors.is_empty() {
+                return Err(ParseError::UnknownPalette { name: name.into() });
+            }
+            output.extend(colors.into_iter().map(|c| (c, ColorFormat::Html)));
+
+            input_i = input_ii.trim_start();
+        } else if let Some((color, input_ii)) = take_alias(input_i).filter(|_| !literal) {
+            output.push((color, ColorFormat::Html));
+            input_i = input_ii.trim_start();
         } else {
             let (word, input_ii) = take_word(input_i).ok_or_else(|| ParseError::ExpectedWord {
                 string: input_i.into(),
             })?;
 
-            let color = if word == "pick" {
+            let color = if literal && matches!(word, "pick" | "rand") {
+                return Err(NotLiteral { got: word.into() });
+            } else if word == "pick" {
                 let color = ColorPicker::new(None, None).display(state)?;
                 (color, color.get_color_format())
             } else if word == "rand" {
@@ -122,25 +170,34 @@ pub fn parse(mut input: &str, state: State) -> Result<Vec<(Color, ColorFormat)>,
                 match hex::parse(word) {
                     Ok(hex) => (Color::Rgb(hex), ColorFormat::Hex),
                     Err(err) => {
+                        let mut similar = Vec::new();
                         if word.chars().all(|c| c.is_ascii_alphabetic()) && word.len() > 3 {
-                            let mut similar = html::get_similar(word);
-                            if !similar.is_empty() {
-                                similar.sort_by(|&(_, l), &(_, r)| {
-                                    if l < r {
-                                        Ordering::Less
-                                    } else if l > r {
-                                        Ordering::Greater
-                                    } else {
-                                        Ordering::Equal
-                                    }
-                                });
-                                let (suggestion, _) = similar[similar.len() - 1];
-                                return Err(ParseError::Misspelled {
-                                    got: word.to_string(),
-                                    suggestion: suggestion.to_string(),
-                                });
+                            similar.extend(html::get_similar(word).into_iter().map(|s| (word, s)));
+                        }
+                        // names of user-defined colors can contain dots and dashes
+                        if let Some((name, _)) = take_name(input_i).filter(|_| !literal) {
+                            if name.len() > 3 {
+                                similar.extend(
+                                    alias::get_similar(name).into_iter().map(|s| (name, s)),
+                                );
                             }
                         }
+                        if !similar.is_empty() {
+                            similar.sort_by(|&(_, (_, l)), &(_, (_, r))| {
+                                if l < r {
+                                    Ordering::Less
+                                } else if l > r {
+                                    Ordering::Greater
+                                } else {
+                                    Ordering::Equal
+                                }
+                            });
+                            let (got, (suggestion, _)) = similar[similar.len() - 1];
+                            return Err(ParseError::Misspelled {
+                                got: got.to_string(),
+                                suggestion: suggestion.to_string(),
+                            });
+                        }
                         return Err(err.into());
                     }
                 }
@@ -246,6 +303,12 @@ fn parse_rand_component(
                     ColorSpace::Xyz if i == 1 => fastrand::u32(0..=100) as f64,
                     ColorSpace::Yxy if i == 0 => fastrand::u32(0..=100) as f64,
                     ColorSpace::Gray => fastrand::f64(),
+                    ColorSpace::Oklab if i == 0 => fastrand::f64(),
+                    ColorSpace::Oklch => match i {
+                        0 => fastrand::f64(),
+                        1 => fastrand::f64() * 0.37,
+                        _ => fastrand::u32(0..360) as f64,
+                    },
                     _ => return Err(ParseError::UnsupportedRand { cs }),
                 },
                 rest,
@@ -262,6 +325,24 @@ fn take_word(input: &str) -> Option<(&str, &str)> {
     Some(res).filter(|(word, _)| !word.is_empty())
 }
 
+/// Like `take_word`, but also accepts dots and dashes, which can appear in the
+/// names of user-defined colors and palettes
+fn take_name(input: &str) -> Option<(&str, &str)> {
+    let res = take_until(input, |c| {
+        !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    });
+    Some(res).filter(|(word, _)| !word.is_empty())
+}
+
+/// Parses the name of a user-defined color, e.g. `brand.primary`
+fn take_alias(input: &str) -> Option<(Color, &str)> {
+    let (name, rest) = take_name(input)?;
+    if matches!(name, "pick" | "rand") {
+        return None;
+    }
+    Some((alias::get(name)?, rest))
+}
+
 fn take_until(input: &str, f: impl FnMut(char) -> bool) -> (&str, &str) {
     let next = input.split(f).next().unwrap_or("");
     let rest = &input[next.len()..];
@@ -0,0 +1,85 @@
+//! Color scales (ramps) for design systems, e.g. Tailwind's 50–950.
+//!
+//! The lightness of the steps is evenly distributed in a perceptual color
+//! space (LCh or OKLCH), and the hue of the seed color is kept. The chroma of
+//! the seed color is eased out linearly towards white and black, so the
+//! lightest steps are only slightly tinted.
+
+use super::{gamut, Color, ColorSpace};
+
+/// The names of Tailwind's 11 steps
+pub const TAILWIND_NAMES: [&str; 11] = [
+    "50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950",
+];
+
+/// Returns `steps` colors from light to dark with the hue of the seed color.
+/// `lightest` and `darkest` are percentages of the lightness range.
+/// `color_space` must be `Lch` or `Oklch`.
+pub fn scale(
+    seed: Color,
+    steps: usize,
+    lightest: f64,
+    darkest: f64,
+    color_space: ColorSpace,
+) -> Vec<Color> {
+    let max_lightness = match color_space {
+        ColorSpace::Lch => 100.0,
+        ColorSpace::Oklch => 1.0,
+        _ => panic!("Can't create a scale in {:?}", color_space),
+    };
+    let (_, lch) = seed.to_color_space(color_space).divide();
+    let seed_lightness = (lch[0] / max_lightness).clamp(0.001, 0.999);
+    let hue = if lch[2].is_nan() { 0.0 } else { lch[2] };
+
+    (0..steps)
+        .map(|i| {
+            let ratio = if steps > 1 {
+                i as f64 / (steps - 1) as f64
+            } else {
+                0.5
+            };
+            let lightness = (lightest + (darkest - lightest) * ratio) / 100.0;
+
+            // how close the step is to the seed color (1), compared to white or black (0)
+            let t = if lightness >= seed_lightness {
+                (1.0 - lightness) / (1.0 - seed_lightness)
+            } else {
+                lightness / seed_lightness
+            };
+            let chroma = lch[1] * t.clamp(0.0, 1.0);
+
+            let color =
+                Color::new_unchecked(color_space, &[lightness * max_lightness, chroma, hue]);
+            gamut::map_to_gamut(color, color_space)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scale;
+    use crate::color::{space::Rgb, Color, ColorSpace};
+
+    #[test]
+    fn test_scale() {
+        let seed = Color::Rgb(Rgb::from_hex(0x3b82f6));
+        for color_space in [ColorSpace::Lch, ColorSpace::Oklch] {
+            let colors = scale(seed, 11, 97.0, 25.0, color_space);
+            assert_eq!(colors.len(), 11);
+
+            let lightness: Vec<f64> = colors
+                .iter()
+                .map(|c| c.to_color_space(ColorSpace::Lch).divide().1[0])
+                .collect();
+            assert!(lightness.windows(2).all(|w| w[0] > w[1]), "{:?}", lightness);
+
+            let chroma = |c: Color| c.to_color_space(color_space).divide().1[1];
+            let (_, seed_lch) = seed.to_color_space(color_space).divide();
+            assert!(chroma(colors[0]) < seed_lch[1] * 0.2);
+            assert!(colors[1..10].iter().any(|&c| chroma(c) > seed_lch[1] * 0.8));
+
+            let hue = colors[5].to_color_space(color_space).divide().1[2];
+            assert!((hue - seed_lch[2]).abs() < 2.0, "{} {}", hue, seed_lch[2]);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Module for finding colors in arbitrary text, e.g. in stylesheets or source
+//! code.
+//!
+//! Unlike `parse`, this doesn't expect the text to consist only of colors.
+//! It recognizes hex colors with a leading `#` (e.g. `#f70`, `#ff7700cc`),
+//! HTML color names and color functions. The CSS functions `rgb()`, `rgba()`,
+//! `hsl()`, `hsla()`, `lab()` and `lch()` are parsed according to CSS rules
+//! (units, percentages, optional alpha channel, D50 white point for `lab()`
+//! and `lch()`), other color spaces supported by colo (e.g. `cmyk()`) are
+//! parsed like in the command line.
+
+use std::f64::consts::PI;
+
+use super::{css, hex, html, Color, ColorFormat, ColorSpace};
+
+/// A color found in a text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<'a> {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number (in characters), starting at 1
+    pub column: usize,
+    /// The text that was recognized as a color
+    pub text: &'a str,
+    pub color: Color,
+    pub format: ColorFormat,
+}
+
+/// Options for `find_colors`
+#[derive(Debug, Copy, Clone)]
+pub struct ScanOptions {
+    /// Whether HTML color names (e.g. `orange`) are recognized
+    pub names: bool,
+}
+
+/// Finds all colors in the text
+pub fn find_colors(text: &str, options: ScanOptions) -> Vec<Match<'_>> {
+    let mut matches = Vec::new();
+    for (line_idx, line) in text.lines().enumerate() {
+        find_colors_in_line(line, line_idx + 1, options, &mut matches);
+    }
+    matches
+}
+
+fn find_colors_in_line<'a>(
+    line: &'a str,
+    line_no: usize,
+    options: ScanOptions,
+    matches: &mut Vec<Match<'a>>,
+) {
+    let mut prev: Option<char> = None;
+    let mut idx = 0;
+
+    while let Some(c) = line[idx..].chars().next() {
+        let at_boundary = !prev.is_some_and(|p| is_ident_char(p) || "&#.$@".contains(p));
+
+        let found = if !at_boundary {
+            None
+        } else if c == '#' {
+            scan_hex(&line[idx..])
+        } else if c.is_ascii_alphabetic() {
+            scan_word(&line[idx..], options)
+        } else {
+            None
+        };
+
+        match found {
+            Some((len, color, format)) => {
+                matches.push(Match {
+                    line: line_no,
+                    column: line[..idx].chars().count() + 1,
+                    text: &line[idx..idx + len],
+                    color,
+                    format,
+                });
+                prev = line[..idx + len].chars().next_back();
+                idx += len;
+            }
+            None => {
+                // skip the rest of the word, so we don't find colors in the
+                // middle of an identifier
+                let len = if is_ident_char(c) {
+                    line[idx..]
+                        .find(|c: char| !is_ident_char(c))
+                        .unwrap_or(line.len() - idx)
+                } else {
+                    c.len_utf8()
+                };
+                prev = line[..idx + len].chars().next_back();
+                idx += len;
+            }
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Recognizes a hex color with 3, 4, 6 or 8 digits. The alpha channel is
+/// ignored.
+fn scan_hex(input: &str) -> Option<(usize, Color, ColorFormat)> {
+    let digits = &input[1..];
+    let len = digits
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(digits.len());
+    if digits[len..].starts_with(is_ident_char) {
+        return None;
+    }
+
+    let rgb_digits = match len {
+        3 | 4 => 3,
+        6 | 8 => 6,
+        _ => return None,
+    };
+    let rgb = hex::parse(&digits[..rgb_digits]).ok()?;
+    Some((len + 1, Color::Rgb(rgb), ColorFormat::Hex))
+}
+
+/// Recognizes an HTML color name or a color function
+fn scan_word(input: &str, options: ScanOptions) -> Option<(usize, Color, ColorFormat)> {
+    let len = input
+        .find(|c: char| !is_ident_char(c))
+        .unwrap_or(input.len());
+    let word = &input[..len];
+    let rest = &input[len..];
+
+    if let Some(args) = rest.strip_prefix('(') {
+        let end = args.find(')')?;
+        let color = parse_function(&word.to_ascii_lowercase(), &args[..end])?;
+        let format = color.get_color_format();
+        Some((len + end + 2, color, format))
+    } else if options.names {
+        let color = html::get(word)?;
+        Some((len, Color::Rgb(color), ColorFormat::Html))
+    } else {
+        None
+    }
+}
+
+/// A number with an optional unit
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Value {
+    Number(f64),
+    Percent(f64),
+    /// An angle in degrees
+    Angle(f64),
+}
+
+impl Value {
+    /// Returns the value, where 100% corresponds to `full`
+    fn scaled(self, full: f64) -> f64 {
+        match self {
+            Value::Number(n) | Value::Angle(n) => n,
+            Value::Percent(p) => p / 100.0 * full,
+        }
+    }
+
+    /// Returns the value as a fraction, where both 100% and 100 correspond to
+    /// 1.0
+    fn fraction(self) -> f64 {
+        match self {
+            Value::Number(n) | Value::Angle(n) => n / 100.0,
+            Value::Percent(p) => p / 100.0,
+        }
+    }
+
+    /// Returns the value as a hue in degrees between 0 and 360
+    fn hue(self) -> f64 {
+        match self {
+            Value::Number(n) | Value::Angle(n) | Value::Percent(n) => n.rem_euclid(360.0),
+        }
+    }
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    if s.eq_ignore_ascii_case("none") {
+        return Some(Value::Number(0.0));
+    }
+    let unit_start = s
+        .find(|c: char| !matches!(c, '0'..='9' | '.' | '-' | '+' | 'e' | 'E'))
+        .unwrap_or(s.len());
+    let num: f64 = s[..unit_start].parse().ok()?;
+
+    Some(match s[unit_start..].to_ascii_lowercase().as_str() {
+        "" => Value::Number(num),
+        "%" => Value::Percent(num),
+        "deg" => Value::Angle(num),
+        "grad" => Value::Angle(num * 0.9),
+        "rad" => Value::Angle(num * 180.0 / PI),
+        "turn" => Value::Angle(num * 360.0),
+        _ => return None,
+    })
+}
+
+/// Parses the arguments of a color function. Both the legacy syntax
+/// (`rgb(255, 0, 0)`) and the modern syntax (`rgb(255 0 0 / 50%)`) are
+/// supported.
+fn parse_function(name: &str, args: &str) -> Option<Color> {
+    let values = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(parse_value)
+        .collect::<Option<Vec<Value>>>()?;
+
+    let (space, has_alpha) = match name {
+        "rgb" | "rgba" => (ColorSpace::Rgb, true),
+        "hsl" | "hsla" => (ColorSpace::Hsl, true),
+        "lab" => (ColorSpace::Lab, true),
+        "lch" => (ColorSpace::Lch, true),
+        _ => (name.parse().ok()?, false),
+    };
+
+    let n = space.num_components();
+    if values.len() != n && !(has_alpha && values.len() == n + 1) {
+        return None;
+    }
+    let v = &values[..n];
+
+    let components = match name {
+        "rgb" | "rgba" => v
+            .iter()
+            .map(|v| v.scaled(255.0).clamp(0.0, 255.0))
+            .collect(),
+        "hsl" | "hsla" => vec![
+            v[0].hue(),
+            v[1].fraction().clamp(0.0, 1.0),
+            v[2].fraction().clamp(0.0, 1.0),
+        ],
+        // CSS defines Lab and LCh with a D50 white point
+        "lab" => {
+            let lab = [v[0].scaled(100.0), v[1].scaled(125.0), v[2].scaled(125.0)];
+            return Some(css::from_lab_d50(lab));
+        }
+        "lch" => {
+            let (c, h) = (v[1].scaled(150.0), v[2].hue().to_radians());
+            let lab = [v[0].scaled(100.0), c * h.cos(), c * h.sin()];
+            return Some(css::from_lab_d50(lab));
+        }
+        _ => v
+            .iter()
+            .map(|&v| match v {
+                Value::Percent(p) => p / 100.0,
+                Value::Number(n) | Value::Angle(n) => n,
+            })
+            .collect::<Vec<_>>(),
+    };
+
+    Color::new(space, &components).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_colors, ScanOptions};
+    use crate::color::{space::Rgb, Color, ColorFormat, CssFunction};
+
+    fn find(text: &str) -> Vec<(usize, usize, &str, String)> {
+        find_colors(text, ScanOptions { names: true })
+            .into_iter()
+            .map(|m| {
+                (
+                    m.line,
+                    m.column,
+                    m.text,
+                    ColorFormat::Hex.format_or_hex(m.color),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_hex() {
+        assert_eq!(
+            find("a { color: #F70; border: 1px solid #ff770080 }"),
+            [
+                (1, 12, "#F70", "#ff7700".into()),
+                (1, 36, "#ff770080", "#ff7700".into()),
+            ]
+        );
+        assert_eq!(find("&#123; #12345 #abcdefg issue#123"), []);
+    }
+
+    #[test]
+    fn test_find_functions() {
+        assert_eq!(
+            find("rgb(255, 0, 0)\n  rgba(100% 50% 0 / .5) hsl(0.5turn 100% 50%)"),
+            [
+                (1, 1, "rgb(255, 0, 0)", "#ff0000".into()),
+                (2, 3, "rgba(100% 50% 0 / .5)", "#ff8000".into()),
+                (2, 25, "hsl(0.5turn 100% 50%)", "#00ffff".into()),
+            ]
+        );
+        assert_eq!(find("cmyk(0%, 100%, 100%, 0%)")[0].3, "#ff0000");
+        assert_eq!(find("rgb(var(--red))"), []);
+        assert_eq!(find("lab(87.82% -79.27 80.99)")[0].3, "#00ff00");
+    }
+
+    #[test]
+    fn test_css_round_trip() {
+        for hex in [0x00ff00, 0xff7700, 0x123456, 0xffffff, 0x000000] {
+            let color = Color::Rgb(Rgb::from_hex(hex));
+            for function in [CssFunction::Lab, CssFunction::Lch] {
+                let css = ColorFormat::Css(function).format_or_hex(color);
+                assert_eq!(
+                    find(&css)[0].3,
+                    ColorFormat::Hex.format_or_hex(color),
+                    "{}",
+                    css
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_names() {
+        assert_eq!(
+            find("color: Orange; background: red-ish; .blue { }; $red"),
+            [(1, 8, "Orange", "#ffa500".into())]
+        );
+        assert_eq!(
+            find_colors("orange #f00", ScanOptions { names: false }).len(),
+            1
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::{fmt, str::FromStr};
 
 pub use super::gray::Gray;
+pub use super::oklab::{Oklab, Oklch};
 pub use color_space::{Cmy, Cmyk, Hsl, Hsv, HunterLab, Lab, Lch, Luv, Rgb, Xyz, Yxy};
 
 /// A C-like enum listing all supported color spaces
@@ -18,22 +19,32 @@ pub enum ColorSpace {
     Xyz,
     Yxy,
     Gray,
+    Oklab,
+    Oklch,
 }
 
 impl ColorSpace {
-    /// Returns the number of color components (between 1 and 4).
-    pub fn num_components(&self) -> usize {
-        match self {
-            ColorSpace::Cmyk => 4,
-            ColorSpace::Gray => 1,
-            _ => 3,
-        }
-    }
-}
+    /// All supported color spaces
+    pub const ALL: [ColorSpace; 14] = [
+        ColorSpace::Rgb,
+        ColorSpace::Cmy,
+        ColorSpace::Cmyk,
+        ColorSpace::Hsv,
+        ColorSpace::Hsl,
+        ColorSpace::Lch,
+        ColorSpace::Luv,
+        ColorSpace::Lab,
+        ColorSpace::HunterLab,
+        ColorSpace::Xyz,
+        ColorSpace::Yxy,
+        ColorSpace::Gray,
+        ColorSpace::Oklab,
+        ColorSpace::Oklch,
+    ];
 
-impl fmt::Display for ColorSpace {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(match self {
+    /// Returns the name of the color space, as it is used in the command line
+    pub fn name(&self) -> &'static str {
+        match self {
             ColorSpace::Rgb => "rgb",
             ColorSpace::Cmy => "cmy",
             ColorSpace::Cmyk => "cmyk",
@@ -46,7 +57,45 @@ impl fmt::Display for ColorSpace {
             ColorSpace::Xyz => "xyz",
             ColorSpace::Yxy => "yxy",
             ColorSpace::Gray => "gry",
-        })
+            ColorSpace::Oklab => "oklab",
+            ColorSpace::Oklch => "oklch",
+        }
+    }
+
+    /// Returns the number of color components (between 1 and 4).
+    pub fn num_components(&self) -> usize {
+        match self {
+            ColorSpace::Cmyk => 4,
+            ColorSpace::Gray => 1,
+            _ => 3,
+        }
+    }
+
+    /// Returns the names of the color components, in the same order as
+    /// returned by `Color::divide`.
+    pub fn component_names(&self) -> &'static [&'static str] {
+        match self {
+            ColorSpace::Rgb => &["r", "g", "b"],
+            ColorSpace::Cmy => &["c", "m", "y"],
+            ColorSpace::Cmyk => &["c", "m", "y", "k"],
+            ColorSpace::Hsv => &["h", "s", "v"],
+            ColorSpace::Hsl => &["h", "s", "l"],
+            ColorSpace::Lch => &["l", "c", "h"],
+            ColorSpace::Luv => &["l", "u", "v"],
+            ColorSpace::Lab => &["l", "a", "b"],
+            ColorSpace::HunterLab => &["l", "a", "b"],
+            ColorSpace::Xyz => &["x", "y", "z"],
+            ColorSpace::Yxy => &["y1", "x", "y2"],
+            ColorSpace::Gray => &["l"],
+            ColorSpace::Oklab => &["l", "a", "b"],
+            ColorSpace::Oklch => &["l", "c", "h"],
+        }
+    }
+}
+
+impl fmt::Display for ColorSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
     }
 }
 
@@ -78,6 +127,8 @@ impl FromStr for ColorSpace {
             "xyz" => ColorSpace::Xyz,
             "yxy" => ColorSpace::Yxy,
             "gry" => ColorSpace::Gray,
+            "oklab" => ColorSpace::Oklab,
+            "oklch" => ColorSpace::Oklch,
             _ => return Err(ColorSpaceParseError),
         })
     }
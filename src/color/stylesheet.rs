@@ -0,0 +1,500 @@
+//! A minimal CSS parser, which is used to find the text and background colors
+//! of each rule in a stylesheet.
+//!
+//! It supports comments, strings, custom properties and nested at-rules such
+//! as `@media`, but not the full cascade: A rule that only sets one of `color`
+//! and `background(-color)` is paired with the value inherited from `:root`,
+//! `html` or `body`, or with the browser default (black on white).
+
+use std::collections::HashMap;
+
+use super::{
+    scan::{self, ScanOptions},
+    space::Rgb,
+    Color, ColorSpace,
+};
+
+/// A style rule, e.g. `a:hover { color: red }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub selector: String,
+    /// Line number of the selector, starting at 1
+    pub line: usize,
+    pub declarations: Vec<Declaration>,
+}
+
+/// A declaration in a style rule, e.g. `color: red`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    /// The property name in lowercase (custom properties are case-sensitive)
+    pub property: String,
+    pub value: String,
+}
+
+/// The text color and background color of a rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorPair {
+    pub selector: String,
+    pub line: usize,
+    pub color: Color,
+    pub background: Color,
+    /// Whether the text color is inherited
+    pub inherited_color: bool,
+    /// Whether the background is inherited
+    pub inherited_background: bool,
+}
+
+/// Parses the style rules of a stylesheet. Rules inside at-rules that contain
+/// style rules (e.g. `@media`) are included, others (e.g. `@font-face`,
+/// `@keyframes`) are skipped.
+pub fn parse_rules(text: &str) -> Vec<Rule> {
+    let text = strip_comments(text);
+    let mut rules = Vec::new();
+    let mut parser = Parser {
+        chars: text.char_indices().collect(),
+        pos: 0,
+        line: 1,
+        text: &text,
+    };
+    parser.parse_block(&mut rules, false);
+    rules
+}
+
+/// Returns the color pairs of all rules that set `color`, `background` or
+/// `background-color` to a color that can be determined
+pub fn color_pairs(rules: &[Rule]) -> Vec<ColorPair> {
+    let mut variables = HashMap::new();
+    for rule in rules {
+        for decl in &rule.declarations {
+            if decl.property.starts_with("--") {
+                variables.insert(decl.property.as_str(), decl.value.as_str());
+            }
+        }
+    }
+
+    let mut default_color = Color::Rgb(Rgb::new(0.0, 0.0, 0.0));
+    let mut default_background = Color::Rgb(Rgb::new(255.0, 255.0, 255.0));
+    let mut pairs = Vec::new();
+
+    for rule in rules {
+        let mut color = None;
+        let mut background = None;
+        for decl in &rule.declarations {
+            match decl.property.as_str() {
+                "color" => color = Some(resolve_color(&decl.value, &variables)),
+                "background" | "background-color" => {
+                    background = Some(resolve_color(&decl.value, &variables))
+                }
+                _ => {}
+            }
+        }
+        // `None` means not set, `Some(None)` means it can't be determined
+        let (color, background) = match (color, background) {
+            (None, None) | (Some(None), _) | (_, Some(None)) => continue,
+            (color, background) => (color.flatten(), background.flatten()),
+        };
+
+        let is_root = rule
+            .selector
+            .split(',')
+            .any(|s| matches!(s.trim(), ":root" | "html" | "body"));
+        pairs.push(ColorPair {
+            selector: rule.selector.clone(),
+            line: rule.line,
+            color: color.unwrap_or(default_color),
+            background: background.unwrap_or(default_background),
+            inherited_color: color.is_none(),
+            inherited_background: background.is_none(),
+        });
+        if is_root {
+            default_color = color.unwrap_or(default_color);
+            default_background = background.unwrap_or(default_background);
+        }
+    }
+    pairs
+}
+
+/// Returns the color of a `color` or `background` value, or `None` if it
+/// can't be determined, e.g. for `inherit`, gradients or undefined variables
+fn resolve_color(value: &str, variables: &HashMap<&str, &str>) -> Option<Color> {
+    let value = resolve_variables(value, variables, 0)?;
+    let lower = value.to_ascii_lowercase();
+    if lower.contains("gradient(") {
+        return None;
+    }
+    let value = strip_functions(&value);
+    let lower = value.to_ascii_lowercase();
+    let keywords = [
+        "transparent",
+        "inherit",
+        "currentcolor",
+        "initial",
+        "unset",
+        "revert",
+    ];
+    if keywords.iter().any(|k| lower.contains(k)) {
+        return None;
+    }
+    let options = ScanOptions { names: true };
+    let first = scan::find_colors(&value, options).into_iter().next()?;
+    Some(first.color)
+}
+
+/// Removes functions that don't specify a color, e.g. `url(tan.png)`, so their
+/// arguments aren't mistaken for colors
+fn strip_functions(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(open) = rest.find('(') {
+        let name_start = rest[..open].trim_end_matches(is_name_char).len();
+        let name = &rest[name_start..open];
+        let is_color_function = matches!(name.to_ascii_lowercase().as_str(), "rgba" | "hsla")
+            || name.parse::<ColorSpace>().is_ok();
+        if is_color_function {
+            result.push_str(&rest[..=open]);
+            rest = &rest[open + 1..];
+            continue;
+        }
+
+        result.push_str(&rest[..name_start]);
+        result.push(' ');
+        let mut nesting = 0;
+        let mut quote = None;
+        let close = rest[open + 1..].find(|c| {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '(') => nesting += 1,
+                (None, ')') if nesting == 0 => return true,
+                (None, ')') => nesting -= 1,
+                _ => {}
+            }
+            false
+        });
+        rest = match close {
+            Some(close) => &rest[open + close + 2..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Replaces `var(--name)` and `var(--name, fallback)` with the value of the
+/// custom property
+fn resolve_variables(value: &str, variables: &HashMap<&str, &str>, depth: u32) -> Option<String> {
+    let start = match value.find("var(") {
+        Some(start) => start,
+        None => return Some(value.to_string()),
+    };
+    if depth > 10 {
+        return None;
+    }
+    let args_start = start + 4;
+    let mut nesting = 0;
+    let end = value[args_start..].find(|c| {
+        match c {
+            '(' => nesting += 1,
+            ')' if nesting == 0 => return true,
+            ')' => nesting -= 1,
+            _ => {}
+        }
+        false
+    })? + args_start;
+
+    let args = &value[args_start..end];
+    let (name, fallback) = match args.find(',') {
+        Some(comma) => (args[..comma].trim(), Some(args[comma + 1..].trim())),
+        None => (args.trim(), None),
+    };
+    let replacement = variables.get(name).copied().or(fallback)?;
+    let replaced = format!("{}{}{}", &value[..start], replacement, &value[end + 1..]);
+    resolve_variables(&replaced, variables, depth + 1)
+}
+
+/// Replaces comments with spaces, keeping line breaks, so line numbers stay
+/// the same. Comment delimiters in strings are ignored.
+fn strip_comments(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut quote = None;
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                result.push(c);
+                result.extend(chars.next());
+                continue;
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '/') if chars.peek() == Some(&'*') => {
+                chars.next();
+                result.push_str("  ");
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    result.push(if c == '\n' { '\n' } else { ' ' });
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        result.push(c);
+    }
+    result
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<char> {
+        let &(_, c) = self.chars.get(self.pos)?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn byte_pos(&self) -> usize {
+        self.chars
+            .get(self.pos)
+            .map_or(self.text.len(), |&(i, _)| i)
+    }
+
+    /// Skips a string, after the opening quote was consumed
+    fn skip_string(&mut self, quote: char) {
+        while let Some(c) = self.next() {
+            match c {
+                '\\' => {
+                    self.next();
+                }
+                c if c == quote => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// Parses rules until the end of the block or the end of the text. Stray
+    /// closing braces at the top level are skipped.
+    fn parse_block(&mut self, rules: &mut Vec<Rule>, nested: bool) {
+        loop {
+            // skip whitespace
+            while let Some(&(_, c)) = self.chars.get(self.pos) {
+                if !c.is_whitespace() {
+                    break;
+                }
+                self.next();
+            }
+            let line = self.line;
+            let start = self.byte_pos();
+
+            // read the prelude
+            let end = loop {
+                match self.next() {
+                    None => return,
+                    Some('}') if nested => return,
+                    Some('}') => break None,
+                    Some(q @ ('"' | '\'')) => self.skip_string(q),
+                    Some(c @ ('{' | ';')) => break Some((self.byte_pos() - c.len_utf8(), c)),
+                    Some(_) => {}
+                }
+            };
+            let (end, terminator) = match end {
+                Some(end) => end,
+                None => continue,
+            };
+            if terminator == ';' {
+                // e.g. `@import url(..);`
+                continue;
+            }
+            let prelude = self.text[start..end].trim();
+
+            if let Some(at_rule) = prelude.strip_prefix('@') {
+                let name = at_rule
+                    .split(|c: char| !is_name_char(c))
+                    .next()
+                    .unwrap_or("");
+                match name.to_ascii_lowercase().as_str() {
+                    "media" | "supports" | "document" | "layer" | "container" | "scope" => {
+                        self.parse_block(rules, true)
+                    }
+                    _ => {
+                        self.read_body();
+                    }
+                }
+            } else {
+                let body_start = self.byte_pos();
+                let body_end = self.read_body();
+                let body = &self.text[body_start..body_end];
+                rules.push(Rule {
+                    selector: prelude.split_whitespace().collect::<Vec<_>>().join(" "),
+                    line,
+                    declarations: parse_declarations(body),
+                });
+            }
+        }
+    }
+
+    /// Skips to the end of a block, after the opening brace was consumed.
+    /// Returns the position of the closing brace.
+    fn read_body(&mut self) -> usize {
+        let mut nesting = 0;
+        loop {
+            let pos = self.byte_pos();
+            match self.next() {
+                None => return pos,
+                Some(q @ ('"' | '\'')) => self.skip_string(q),
+                Some('{') => nesting += 1,
+                Some('}') if nesting == 0 => return pos,
+                Some('}') => nesting -= 1,
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-'
+}
+
+/// Parses the declarations in a rule body, e.g. `color: red; margin: 0`
+fn parse_declarations(body: &str) -> Vec<Declaration> {
+    let mut declarations = Vec::new();
+    let mut nesting = 0;
+    let mut quote = None;
+    let mut start = 0;
+
+    for (i, c) in body
+        .char_indices()
+        .chain(std::iter::once((body.len(), ';')))
+    {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => nesting += 1,
+            (None, ')') => nesting -= 1,
+            (None, ';') if nesting <= 0 => {
+                if let Some((property, value)) = body[start..i].split_once(':') {
+                    let property = property.trim();
+                    let property = if property.starts_with("--") {
+                        property.to_string()
+                    } else {
+                        property.to_ascii_lowercase()
+                    };
+                    let value = value.trim().trim_end_matches("!important").trim();
+                    declarations.push(Declaration {
+                        property,
+                        value: value.to_string(),
+                    });
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{color_pairs, parse_rules};
+    use crate::color::{space::Rgb, Color};
+
+    const CSS: &str = r#"
+/* colors */
+:root {
+    --brand: #ff7700;
+    --text: #333;
+}
+
+body { background: #fafafa; color: var(--text) }
+
+@media (prefers-color-scheme: dark) {
+    .card,
+    .panel { background-color: navy; }
+}
+
+@font-face { font-family: "x{"; }
+
+.button { color: white; background: var(--brand) !important; }
+.icon { color: currentColor; }
+.hero { background: linear-gradient(red, blue); color: white; }
+.logo { background: url(tan.png) rgb(0 0 255); }
+.banner { background: url("red.png"); }
+"#;
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = parse_rules(CSS);
+        let selectors: Vec<_> = rules
+            .iter()
+            .map(|r| (r.selector.as_str(), r.line))
+            .collect();
+        assert_eq!(
+            selectors,
+            [
+                (":root", 3),
+                ("body", 8),
+                (".card, .panel", 11),
+                (".button", 17),
+                (".icon", 18),
+                (".hero", 19),
+                (".logo", 20),
+                (".banner", 21),
+            ]
+        );
+        assert_eq!(rules[3].declarations[1].value, "var(--brand)");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let css =
+            "a { content: \"/*\"; color: red }\n} b { color: blue }\n/* } */ c { color: lime }";
+        let rules = parse_rules(css);
+        let selectors: Vec<_> = rules
+            .iter()
+            .map(|r| (r.selector.as_str(), r.line))
+            .collect();
+        assert_eq!(selectors, [("a", 1), ("b", 2), ("c", 3)]);
+        assert_eq!(rules[0].declarations[0].value, "\"/*\"");
+    }
+
+    #[test]
+    fn test_color_pairs() {
+        let rgb = |hex| Color::Rgb(Rgb::from_hex(hex));
+        let pairs = color_pairs(&parse_rules(CSS));
+        let pairs: Vec<_> = pairs
+            .iter()
+            .map(|p| {
+                (
+                    p.selector.as_str(),
+                    p.color,
+                    p.background,
+                    p.inherited_color,
+                )
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("body", rgb(0x333333), rgb(0xfafafa), false),
+                (".card, .panel", rgb(0x333333), rgb(0x000080), true),
+                (".button", rgb(0xffffff), rgb(0xff7700), false),
+                (".logo", rgb(0x333333), rgb(0x0000ff), true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Output templates with placeholders, e.g. `{r} {g} {b}` or `{h:.0}deg`.
+//!
+//! A placeholder has the form `{key}`, `{key:spec}` or `{key*factor:spec}`.
+//! The key is one of the following:
+//!
+//! - a color component, qualified with the color space, e.g. `hsl.h`, `lab.a`
+//! - a short component name: `r`, `g`, `b` (RGB), `h`, `s`, `l` (HSL), `v`
+//!   (HSV), `c`, `m`, `y`, `k` (CMYK)
+//! - a color space, e.g. `hsl`, which is replaced with the color in that color
+//!   space, as it would be printed with `--out hsl`
+//! - `hex`, `html` or `nearest` (the name of the closest named color)
+//!
+//! A component can be scaled by appending `*factor` or `/divisor` to the key,
+//! e.g. `{r/255}`. The spec can contain a precision (e.g. `.2`), and a `%` sign
+//! to multiply the value by 100 and append a percent sign. `hex` supports the
+//! specs `upper` and `lower`. Literal braces are written as `{{` and `}}`.
+
+use std::str::FromStr;
+use thiserror::Error;
+
+use super::{hex, Color, ColorFormat, ColorSpace};
+use color_space::ToRgb;
+
+/// Error caused by parsing an invalid template
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum TemplateError {
+    #[error("Unclosed placeholder in {template:?}, expected '}}'")]
+    Unclosed { template: String },
+    #[error("Unmatched '}}' in {template:?}, use '}}}}' to print a closing brace")]
+    Unmatched { template: String },
+    #[error("Unknown placeholder {key:?}")]
+    UnknownKey { key: String },
+    #[error("Invalid format spec {spec:?} for placeholder {key:?}")]
+    InvalidSpec { key: String, spec: String },
+    #[error("Invalid scaling factor {factor:?}")]
+    InvalidFactor { factor: String },
+}
+
+/// A parsed output template
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Component {
+        space: ColorSpace,
+        index: usize,
+        factor: f64,
+        precision: Option<usize>,
+        percent: bool,
+    },
+    Format(ColorFormat),
+    Hex {
+        upper: bool,
+    },
+}
+
+impl Template {
+    /// Renders the template for the given color
+    pub fn render(&self, color: Color) -> String {
+        let mut s = String::new();
+        for part in &self.parts {
+            match *part {
+                Part::Literal(ref lit) => s.push_str(lit),
+                Part::Component {
+                    space,
+                    index,
+                    factor,
+                    precision,
+                    percent,
+                } => {
+                    let (_, parts) = color.to_color_space(space).divide();
+                    // adding 0.0 turns -0.0 into 0.0
+                    let mut value = parts[index] * factor + 0.0;
+                    if percent {
+                        value *= 100.0;
+                    }
+                    match precision {
+                        Some(p) => s.push_str(&format!("{:.*}", p, value)),
+                        None => s.push_str(&((value * 10.0).round() / 10.0 + 0.0).to_string()),
+                    }
+                    if percent {
+                        s.push('%');
+                    }
+                }
+                Part::Format(format) => s.push_str(&format.format_or_hex(color)),
+                Part::Hex { upper } => {
+                    let hex = hex::rgb_to_u32(color.to_rgb());
+                    match upper {
+                        true => s.push_str(&format!("#{:06X}", hex)),
+                        false => s.push_str(&format!("#{:06x}", hex)),
+                    }
+                }
+            }
+        }
+        s
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+
+        while let Some(idx) = rest.find(['{', '}']) {
+            literal.push_str(&rest[..idx]);
+            rest = &rest[idx..];
+
+            if let Some(r) = rest.strip_prefix("{{") {
+                literal.push('{');
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("}}") {
+                literal.push('}');
+                rest = r;
+            } else if rest.starts_with('}') {
+                return Err(TemplateError::Unmatched {
+                    template: template.into(),
+                });
+            } else {
+                let end = rest.find('}').ok_or_else(|| TemplateError::Unclosed {
+                    template: template.into(),
+                })?;
+                if !literal.is_empty() {
+                    parts.push(Part::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(parse_placeholder(&rest[1..end])?);
+                rest = &rest[end + 1..];
+            }
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Template { parts })
+    }
+}
+
+fn parse_placeholder(placeholder: &str) -> Result<Part, TemplateError> {
+    let (key, spec) = match placeholder.find(':') {
+        Some(idx) => (&placeholder[..idx], Some(&placeholder[idx + 1..])),
+        None => (placeholder, None),
+    };
+    let (key, factor) = match key.find(['*', '/']) {
+        Some(idx) => {
+            let factor = &key[idx + 1..];
+            let n: f64 = factor
+                .trim()
+                .parse()
+                .map_err(|_| TemplateError::InvalidFactor {
+                    factor: factor.into(),
+                })?;
+            let n = if key[idx..].starts_with('/') {
+                1.0 / n
+            } else {
+                n
+            };
+            (key[..idx].trim(), Some(n))
+        }
+        None => (key.trim(), None),
+    };
+    let invalid_spec = || TemplateError::InvalidSpec {
+        key: key.into(),
+        spec: spec.unwrap_or_default().into(),
+    };
+    let lower = key.to_ascii_lowercase();
+
+    // keys that aren't numbers
+    let non_numeric = match lower.as_str() {
+        "hex" => Some(Part::Hex {
+            upper: match spec {
+                None | Some("lower") => false,
+                Some("upper") => true,
+                Some(_) => return Err(invalid_spec()),
+            },
+        }),
+        "html" => Some(Part::Format(ColorFormat::Html)),
+        "nearest" => Some(Part::Format(ColorFormat::NearestName)),
+        s => s
+            .parse()
+            .ok()
+            .map(|cs| Part::Format(ColorFormat::Normal(cs))),
+    };
+    if let Some(part) = non_numeric {
+        if factor.is_some() || (spec.is_some() && !matches!(part, Part::Hex { .. })) {
+            return Err(invalid_spec());
+        }
+        return Ok(part);
+    }
+
+    let (space, index) =
+        find_component(&lower).ok_or_else(|| TemplateError::UnknownKey { key: key.into() })?;
+
+    let spec = spec.unwrap_or_default();
+    let (spec, percent) = match spec.strip_suffix('%') {
+        Some(s) => (s, true),
+        None => (spec, false),
+    };
+    let precision = match spec {
+        "" => None,
+        s => Some(
+            s.strip_prefix('.')
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(invalid_spec)?,
+        ),
+    };
+
+    Ok(Part::Component {
+        space,
+        index,
+        factor: factor.unwrap_or(1.0),
+        precision,
+        percent,
+    })
+}
+
+/// Finds a color component by its name, e.g. `r` or `lab.l`
+fn find_component(key: &str) -> Option<(ColorSpace, usize)> {
+    let (space, name) = match key.find('.') {
+        Some(idx) => (key[..idx].parse().ok()?, &key[idx + 1..]),
+        None => match key {
+            "r" | "g" | "b" => (ColorSpace::Rgb, key),
+            "h" | "s" | "l" => (ColorSpace::Hsl, key),
+            "v" => (ColorSpace::Hsv, key),
+            "c" | "m" | "y" | "k" => (ColorSpace::Cmyk, key),
+            _ => return None,
+        },
+    };
+    let index = space.component_names().iter().position(|&c| c == name)?;
+    Some((space, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Template;
+    use crate::color::{space::Rgb, Color};
+
+    fn render(template: &str, color: Color) -> String {
+        template.parse::<Template>().unwrap().render(color)
+    }
+
+    #[test]
+    fn test_render() {
+        let orange = Color::Rgb(Rgb::new(255.0, 119.0, 0.0));
+
+        assert_eq!(render("{r} {g} {b}", orange), "255 119 0");
+        assert_eq!(render("{r/255:.3},{g*2}", orange), "1.000,238");
+        assert_eq!(
+            render("{h:.0}deg {s:%} {hsl.l:.2}", orange),
+            "28deg 100% 0.50"
+        );
+        assert_eq!(render("{hex:upper} {hex}", orange), "#FF7700 #ff7700");
+        assert_eq!(render("{{{rgb}}}", orange), "{rgb(255, 119, 0)}");
+        assert_eq!(render("{lab.l:.0}", orange), "65");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!("{r".parse::<Template>().is_err());
+        assert!("r}".parse::<Template>().is_err());
+        assert!("{foo}".parse::<Template>().is_err());
+        assert!("{rgb.q}".parse::<Template>().is_err());
+        assert!("{r:x}".parse::<Template>().is_err());
+        assert!("{hex:.2}".parse::<Template>().is_err());
+        assert!("{rgb:.2}".parse::<Template>().is_err());
+    }
+}
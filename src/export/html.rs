@@ -0,0 +1,60 @@
+use std::fmt::Write;
+
+use super::{escape, Sheet};
+use crate::color::ColorFormat;
+
+const STYLE: &str = "\
+body { margin: 16px; background: #fff; color: #000;
+       font: 14px ui-monospace, Menlo, Consolas, monospace; }
+.swatch { display: flex; gap: 16px; margin-bottom: 16px; }
+.square { flex: none; width: 72px; height: 72px; border-radius: 4px;
+          border: 1px solid rgba(0, 0, 0, 0.15); }
+.lines div { white-space: pre; line-height: 18px; }
+.strong { font-weight: bold; }
+.dim { color: #666; }";
+
+/// Renders a swatch sheet as a self-contained HTML document
+pub fn render(sheet: &Sheet) -> String {
+    let mut body = String::new();
+
+    for swatch in sheet.swatches {
+        let hex = ColorFormat::Hex.format_or_hex(swatch.color);
+
+        writeln!(body, r#"<div class="swatch">"#).unwrap();
+        writeln!(
+            body,
+            r#"  <div class="square" style="background: {}"></div>"#,
+            hex
+        )
+        .unwrap();
+        writeln!(body, r#"  <div class="lines">"#).unwrap();
+        for line in sheet.lines(swatch) {
+            let class = if line.strong { "strong" } else { "dim" };
+            writeln!(
+                body,
+                r#"    <div class="{}">{}</div>"#,
+                class,
+                escape(&line.text)
+            )
+            .unwrap();
+        }
+        writeln!(body, "  </div>\n</div>").unwrap();
+    }
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Colors</title>
+<style>
+{}
+</style>
+</head>
+<body>
+{}</body>
+</html>
+",
+        STYLE, body
+    )
+}
@@ -0,0 +1,127 @@
+use color_space::ToRgb;
+
+use crate::color::Color;
+
+/// An RGB image with 8 bits per channel
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// The pixels, row by row
+    pub pixels: Vec<[u8; 3]>,
+}
+
+/// 4x4 Bayer matrix for ordered dithering
+const BAYER: [[f64; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+impl Image {
+    /// Creates an image with vertical stripes of equal width, one for each
+    /// color. The image is black if there are no colors.
+    pub fn stripes(colors: &[Color], width: u32, height: u32) -> Self {
+        let colors: Vec<[u8; 3]> = colors.iter().map(|&c| to_bytes(c, 0.0)).collect();
+        let n = colors.len();
+        let row: Vec<[u8; 3]> = (0..width as usize)
+            .map(|x| {
+                colors
+                    .get(x * n / width as usize)
+                    .copied()
+                    .unwrap_or([0; 3])
+            })
+            .collect();
+        Self::from_rows(width, height, |_| row.clone())
+    }
+
+    /// Creates an image of a horizontal gradient. The function is called with
+    /// a value between 0 and 1 for each column.
+    pub fn gradient(
+        gradient: impl Fn(f64) -> Color,
+        width: u32,
+        height: u32,
+        dither: bool,
+    ) -> Self {
+        let max = (width.max(2) - 1) as f64;
+        let colors: Vec<Color> = (0..width).map(|x| gradient(x as f64 / max)).collect();
+
+        Self::from_rows(width, height, |y| {
+            colors
+                .iter()
+                .enumerate()
+                .map(|(x, &c)| {
+                    let offset = if dither {
+                        (BAYER[y % 4][x % 4] + 0.5) / 16.0 - 0.5
+                    } else {
+                        0.0
+                    };
+                    to_bytes(c, offset)
+                })
+                .collect()
+        })
+    }
+
+    fn from_rows(width: u32, height: u32, row: impl Fn(usize) -> Vec<[u8; 3]>) -> Self {
+        let pixels = (0..height as usize).flat_map(row).collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Encodes the image as binary PPM (portable pixmap)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let header = format!("P6\n{} {}\n255\n", self.width, self.height);
+        self.with_header(header)
+    }
+
+    /// Encodes the image as PAM (portable arbitrary map)
+    pub fn to_pam(&self) -> Vec<u8> {
+        let header = format!(
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 3\nMAXVAL 255\nTUPLTYPE RGB\nENDHDR\n",
+            self.width, self.height
+        );
+        self.with_header(header)
+    }
+
+    fn with_header(&self, header: String) -> Vec<u8> {
+        let mut data = header.into_bytes();
+        data.extend(self.pixels.iter().flatten());
+        data
+    }
+}
+
+/// Converts a color to 8-bit RGB. The offset (between -0.5 and 0.5) is added
+/// before rounding, which is used for dithering.
+fn to_bytes(color: Color, offset: f64) -> [u8; 3] {
+    let rgb = color.to_rgb();
+    [rgb.r, rgb.g, rgb.b].map(|n| (n + offset).round().clamp(0.0, 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Image;
+    use crate::color::{space::Rgb, Color, ColorSpace};
+
+    #[test]
+    fn test_image() {
+        let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0));
+        let white = Color::Rgb(Rgb::new(255.0, 255.0, 255.0));
+
+        let image = Image::stripes(&[black, white], 4, 2);
+        assert_eq!(image.pixels.len(), 8);
+        assert_eq!(image.pixels[1], [0, 0, 0]);
+        assert_eq!(image.pixels[2], [255, 255, 255]);
+        assert!(image.to_ppm().starts_with(b"P6\n4 2\n255\n\0\0\0"));
+
+        let image = Image::gradient(|r| white.mix_with(black, ColorSpace::Rgb, r), 3, 1, false);
+        assert_eq!(image.pixels.len(), 3);
+        assert_eq!(image.pixels[0], [0, 0, 0]);
+        assert_eq!(image.pixels[2], [255, 255, 255]);
+
+        let image = Image::stripes(&[], 2, 1);
+        assert_eq!(image.pixels, vec![[0, 0, 0]; 2]);
+    }
+}
@@ -0,0 +1,51 @@
+//! Exporting a matrix of values for each pair of colors, e.g. the contrast
+//! ratios of a palette.
+
+use crate::{
+    color::{Color, ColorFormat},
+    terminal::json::Json,
+};
+
+/// A square matrix, where `values[row][column]` belongs to the colors
+/// `colors[row]` and `colors[column]`
+pub struct Matrix<'a> {
+    pub colors: &'a [Color],
+    pub values: &'a [Vec<f64>],
+    /// The name of the values, used as key in JSON
+    pub name: &'static str,
+}
+
+impl Matrix<'_> {
+    fn labels(&self) -> Vec<String> {
+        self.colors
+            .iter()
+            .map(|&c| ColorFormat::Hex.format_or_hex(c))
+            .collect()
+    }
+
+    /// Returns the matrix as CSV, with the colors as row and column headers
+    pub fn to_csv(&self) -> String {
+        let labels = self.labels();
+        let mut csv = format!(",{}\n", labels.join(","));
+        for (label, row) in labels.iter().zip(self.values) {
+            let row: Vec<String> = row.iter().map(|v| format!("{:.2}", v)).collect();
+            csv.push_str(&format!("{},{}\n", label, row.join(",")));
+        }
+        csv
+    }
+
+    /// Returns the matrix as JSON object with the keys `colors` and `name`
+    pub fn to_json(&self) -> String {
+        let labels = self.labels().into_iter().map(Json::from).collect();
+        let rows = self
+            .values
+            .iter()
+            .map(|row| Json::Array(row.iter().map(|&v| v.into()).collect()))
+            .collect();
+        let json = Json::Object(vec![
+            ("colors", Json::Array(labels)),
+            (self.name, Json::Array(rows)),
+        ]);
+        format!("{}\n", json)
+    }
+}
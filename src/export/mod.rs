@@ -0,0 +1,270 @@
+//! Exporting colors to files, e.g. swatch sheets that can be embedded in
+//! documentation.
+//!
+//! The file format is determined by the file extension. Swatch sheets can be
+//! exported as SVG or HTML documents, or as raster images (PPM, PAM or PNG),
+//! which only contain the colors.
+//!
+//! Matrices of color pairs can be exported as CSV or JSON.
+
+mod html;
+mod image;
+mod matrix;
+mod png;
+mod svg;
+
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path};
+
+use self::image::Image;
+pub use self::matrix::Matrix;
+use crate::color::{
+    format::{NumberFormat, PREFERRED_FORMATS},
+    Color, ColorFormat,
+};
+
+/// A color in a swatch sheet, optionally with a name
+pub struct Swatch {
+    pub name: Option<String>,
+    pub color: Color,
+}
+
+impl Swatch {
+    pub fn new(color: Color) -> Self {
+        Swatch { name: None, color }
+    }
+
+    pub fn with_name(name: impl Into<String>, color: Color) -> Self {
+        Swatch {
+            name: Some(name.into()),
+            color,
+        }
+    }
+}
+
+/// A list of colors, printed in the given formats
+pub struct Sheet<'a> {
+    pub swatches: &'a [Swatch],
+    pub formats: &'a [ColorFormat],
+    pub number_format: NumberFormat,
+    pub image: ImageOptions,
+}
+
+/// The maximum width and height of raster images in pixels
+pub const MAX_IMAGE_SIZE: u32 = 10_000;
+
+/// Options for raster images
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ImageOptions {
+    /// The width in pixels. Defaults to 64 pixels per swatch, or 512 pixels
+    /// for gradients.
+    pub width: Option<u32>,
+    /// The height in pixels. Defaults to 64 pixels.
+    pub height: Option<u32>,
+    /// Use ordered dithering when rendering gradients
+    pub dither: bool,
+}
+
+/// The supported file types
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum FileType {
+    Svg,
+    Html,
+    Ppm,
+    Pam,
+    Png,
+}
+
+impl FileType {
+    fn from_path(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+
+        Ok(match extension.as_deref() {
+            Some("svg") => FileType::Svg,
+            Some("html") | Some("htm") => FileType::Html,
+            Some("ppm") => FileType::Ppm,
+            Some("pam") => FileType::Pam,
+            Some("png") => FileType::Png,
+            _ => bail!(
+                "Unsupported file type {:?}, expected .svg, .html, .ppm, .pam or .png",
+                path.display()
+            ),
+        })
+    }
+}
+
+/// A line of text next to a swatch
+struct Line {
+    text: String,
+    strong: bool,
+}
+
+impl Sheet<'_> {
+    /// Returns the lines of text that are displayed next to the swatch. As in
+    /// the terminal, the name and the output formats are highlighted, followed
+    /// by the color in a few other formats.
+    fn lines(&self, swatch: &Swatch) -> Vec<Line> {
+        let num = self.number_format;
+        let name = match &swatch.name {
+            Some(name) => name.clone(),
+            None => ColorFormat::NearestName.format_or_hex_with(swatch.color, num),
+        };
+        let outputs: Vec<String> = self
+            .formats
+            .iter()
+            .map(|f| f.format_or_hex_with(swatch.color, num))
+            .collect();
+
+        let mut lines = vec![
+            Line {
+                text: name,
+                strong: true,
+            },
+            Line {
+                text: outputs.join("  "),
+                strong: true,
+            },
+        ];
+        for row in &PREFERRED_FORMATS[1..4] {
+            let row: Vec<String> = row
+                .iter()
+                .filter(|f| !self.formats.contains(f))
+                .filter_map(|f| f.format_with(swatch.color, num))
+                .collect();
+            if !row.is_empty() {
+                lines.push(Line {
+                    text: row.join("  "),
+                    strong: false,
+                });
+            }
+        }
+        lines
+    }
+}
+
+/// Writes a swatch sheet to a file. Raster images contain a vertical stripe
+/// for each color.
+pub fn export(path: &Path, sheet: &Sheet) -> Result<()> {
+    if sheet.swatches.is_empty() {
+        bail!("There are no colors to export");
+    }
+    let image = || {
+        let colors: Vec<Color> = sheet.swatches.iter().map(|s| s.color).collect();
+        let default_width = (64 * colors.len()).min(MAX_IMAGE_SIZE as usize) as u32;
+        let width = sheet.image.width.unwrap_or(default_width);
+        Image::stripes(&colors, width, sheet.image.height.unwrap_or(64))
+    };
+    write(path, sheet, image)
+}
+
+/// Writes a gradient to a file. Raster images are sampled at full resolution,
+/// where `gradient` is called with a value between 0 and 1 for each column.
+/// Other file types contain the swatches of the sheet.
+pub fn export_gradient(path: &Path, sheet: &Sheet, gradient: impl Fn(f64) -> Color) -> Result<()> {
+    let image = || {
+        let width = sheet.image.width.unwrap_or(512);
+        let height = sheet.image.height.unwrap_or(64);
+        Image::gradient(gradient, width, height, sheet.image.dither)
+    };
+    write(path, sheet, image)
+}
+
+fn write(path: &Path, sheet: &Sheet, image: impl FnOnce() -> Image) -> Result<()> {
+    let content = match FileType::from_path(path)? {
+        FileType::Svg => svg::render(sheet).into_bytes(),
+        FileType::Html => html::render(sheet).into_bytes(),
+        FileType::Ppm => image().to_ppm(),
+        FileType::Pam => image().to_pam(),
+        FileType::Png => png::encode(&image()),
+    };
+    fs::write(path, content).with_context(|| format!("Could not write {:?}", path.display()))
+}
+
+/// Writes a matrix to a CSV or JSON file
+pub fn export_matrix(path: &Path, matrix: &Matrix) -> Result<()> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+
+    let content = match extension.as_deref() {
+        Some("csv") => matrix.to_csv(),
+        Some("json") => matrix.to_json(),
+        _ => bail!(
+            "Unsupported file type {:?}, expected .csv or .json",
+            path.display()
+        ),
+    };
+    fs::write(path, content).with_context(|| format!("Could not write {:?}", path.display()))
+}
+
+/// Escapes the characters `<`, `>`, `&` and `"` for XML and HTML
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{html, svg, ImageOptions, Matrix, Sheet, Swatch};
+    use crate::color::{format::NumberFormat, space::Rgb, Color, ColorFormat};
+
+    #[test]
+    fn test_render() {
+        let swatches = [
+            Swatch::new(Color::Rgb(Rgb::new(255.0, 119.0, 0.0))),
+            Swatch::with_name("<brand>", Color::Rgb(Rgb::new(0.0, 0.0, 255.0))),
+        ];
+        let sheet = Sheet {
+            swatches: &swatches,
+            formats: &[ColorFormat::Hex],
+            number_format: NumberFormat::default(),
+            image: ImageOptions::default(),
+        };
+
+        let svg = svg::render(&sheet);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r##"fill="#ff7700""##));
+        assert!(svg.contains("~darkorange (ΔE 6.7)"));
+        assert!(svg.contains("&lt;brand&gt;"));
+
+        let html = html::render(&sheet);
+        assert!(html.contains("background: #0000ff"));
+        assert!(html.contains("&lt;brand&gt;"));
+        assert!(html.contains("hsl(28, 100%, 50%)  hsv(28, 100%, 100%)"));
+    }
+
+    #[test]
+    fn test_matrix() {
+        let colors = [
+            Color::Rgb(Rgb::new(255.0, 255.0, 255.0)),
+            Color::Rgb(Rgb::new(0.0, 0.0, 0.0)),
+        ];
+        let values = [vec![1.0, 21.0], vec![21.0, 1.0]];
+        let matrix = Matrix {
+            colors: &colors,
+            values: &values,
+            name: "contrast",
+        };
+        assert_eq!(
+            matrix.to_csv(),
+            ",#ffffff,#000000\n#ffffff,1.00,21.00\n#000000,21.00,1.00\n"
+        );
+        assert_eq!(
+            matrix.to_json(),
+            "{\"colors\":[\"#ffffff\",\"#000000\"],\"contrast\":[[1,21],[21,1]]}\n"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt::Write;
+
+use super::{escape, Sheet};
+use crate::color::ColorFormat;
+
+const PADDING: usize = 16;
+const SQUARE: usize = 72;
+const LINE_HEIGHT: usize = 18;
+const FONT_SIZE: usize = 13;
+/// Approximate width of a character in a monospace font
+const CHAR_WIDTH: f64 = 7.9;
+
+/// Renders a swatch sheet as a standalone SVG image
+pub fn render(sheet: &Sheet) -> String {
+    let mut body = String::new();
+    let mut y = PADDING;
+    let mut max_chars = 0;
+    let text_x = PADDING * 2 + SQUARE;
+
+    for swatch in sheet.swatches {
+        let hex = ColorFormat::Hex.format_or_hex(swatch.color);
+        let lines = sheet.lines(swatch);
+
+        writeln!(
+            body,
+            r##"  <rect x="{}" y="{}" width="{}" height="{}" rx="4" fill="{}" stroke="#000" stroke-opacity="0.15"/>"##,
+            PADDING, y, SQUARE, SQUARE, hex,
+        )
+        .unwrap();
+
+        for (i, line) in lines.iter().enumerate() {
+            max_chars = max_chars.max(line.text.chars().count());
+            let style = if line.strong {
+                r#"font-weight="bold""#
+            } else {
+                r##"fill="#666""##
+            };
+            writeln!(
+                body,
+                r#"  <text x="{}" y="{}" {}>{}</text>"#,
+                text_x,
+                y + FONT_SIZE + i * LINE_HEIGHT,
+                style,
+                escape(&line.text),
+            )
+            .unwrap();
+        }
+
+        y += SQUARE.max(lines.len() * LINE_HEIGHT) + PADDING;
+    }
+
+    let width = text_x + (max_chars as f64 * CHAR_WIDTH).ceil() as usize + PADDING;
+    let height = y;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="ui-monospace, Menlo, Consolas, monospace" font-size="{font_size}">
+  <rect width="100%" height="100%" fill="#fff"/>
+{body}</svg>
+"##,
+        w = width,
+        h = height,
+        font_size = FONT_SIZE,
+        body = body,
+    )
+}
@@ -7,6 +7,7 @@ use cli::MainCmd;
 
 mod cli;
 mod color;
+mod export;
 mod terminal;
 
 /// The application state. This is currently passed to all functions that need it.
@@ -14,6 +15,7 @@ mod terminal;
 pub struct State {
     interactive: bool,
     color: bool,
+    json: bool,
 }
 
 /// Entry point for the application.
@@ -25,6 +27,7 @@ fn main() -> Result<()> {
     let mut state = State {
         interactive: atty::is(Stream::Stdin),
         color: atty::is(Stream::Stdout),
+        json: false,
     };
     MainCmd::main(&mut state)
 }
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{stdout, Write};
+
+use crate::{
+    color::{scan::Match, Color, ColorFormat},
+    terminal::json::Json,
+    State,
+};
+
+/// Prints the colors found in a file, one per line, with their location
+pub(crate) fn print_matches(
+    state: State,
+    path: &str,
+    matches: &[Match<'_>],
+    output: ColorFormat,
+) -> Result<()> {
+    let mut stdout = stdout();
+
+    for m in matches {
+        let location = format!("{}:{}:{}", path, m.line, m.column);
+        let value = output.format_or_hex(m.color);
+
+        if state.json {
+            Json::Object(vec![
+                ("path", path.into()),
+                ("line", m.line.into()),
+                ("column", m.column.into()),
+                ("text", m.text.into()),
+                ("color", m.color.into()),
+            ])
+            .print()?;
+        } else if state.color {
+            writeln!(
+                stdout,
+                "{}  {}  {:25}  {}",
+                "██".color(m.color.to_term_color()),
+                location.dimmed(),
+                value.bold(),
+                m.text.dimmed(),
+            )?;
+        } else {
+            writeln!(stdout, "{}\t{}\t{}", location, value, m.text)?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints distinct colors with the number of their occurrences
+pub(crate) fn print_color_counts(
+    state: State,
+    colors: impl IntoIterator<Item = (Color, usize)>,
+    output: ColorFormat,
+) -> Result<()> {
+    let mut stdout = stdout();
+
+    for (color, count) in colors {
+        let value = output.format_or_hex(color);
+
+        if state.json {
+            Json::Object(vec![("count", count.into()), ("color", color.into())]).print()?;
+        } else if state.color {
+            writeln!(
+                stdout,
+                "{}  {:>6}  {}",
+                "██".color(color.to_term_color()),
+                count.to_string().dimmed(),
+                value.bold(),
+            )?;
+        } else {
+            writeln!(stdout, "{}\t{}", count, value)?;
+        }
+    }
+    Ok(())
+}
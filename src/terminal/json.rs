@@ -0,0 +1,220 @@
+//! Machine-readable output, enabled with the `--json` flag.
+//!
+//! Commands that output a single result print one JSON object. Commands that
+//! output a stream of results (e.g. `show` or `gradient`) print one JSON object
+//! per line ([NDJSON](http://ndjson.org/)).
+//!
+//! A color is represented as an object with the keys `hex`, `name` (the HTML or
+//! user-defined name, or `null`) and one key for each color space, containing
+//! the color components, e.g.
+//!
+//! ```json
+//! {"hex":"#ff7700","name":null,"rgb":{"r":255,"g":119,"b":0},"hsl":{...},...}
+//! ```
+//!
+//! The results of the commands are:
+//!
+//! - `show`, `pick`: one color per line
+//! - `adjust`: one color per line, with the adjustments applied
+//! - `harmony`: one `{"scheme":..,"base":..,"colors":[..]}` object per input
+//!   color, where `base` is the input color
+//! - `mix`: `{"colors":[{"color":..,"weight":..}],"color_space":..,"result":..}`
+//! - `gradient`: one `{"position":..,"color":..}` object per line, where the
+//!   position is between 0 and 1
+//! - `show` and `gradient` with `--daltonize` print one more line with the
+//!   minimum difference between the simulated colors before and after
+//!   daltonization: `{"simulation":..,"metric":..,"before":..,"after":..}`
+//! - `contrast`: `{"color1":..,"color2":..,"luminance1":..,"luminance2":..,
+//!   "contrast":..,"wcag":{"AA":..,"AAA":..,"AA-large":..,"AAA-large":..,
+//!   "non-text":..}}`, or with `--algorithm apca`: `{"text":..,"background":..,
+//!   "algorithm":"apca","contrast":..,"non_text":..,"font_sizes":[{"weight":..,
+//!   "size":..}]}`, or with `--fix`: `{"text":..,"background":..,
+//!   "fixed":"text color"|"background","color":..,"contrast_before":..,
+//!   "contrast_after":..,"difference":..}`
+//!   With more than 2 colors or `--matrix`: `{"colors":[..],"algorithm":..,
+//!   "contrast":[[..]]}`, where `contrast[i][j]` is the contrast of color `i`
+//!   on color `j`
+//! - `diff`: one `{"color1":..,"color2":..,"metric":..,"difference":..,
+//!   "lightness":..,"chroma":..,"hue":..,"tolerance":..,"pass":..}` object per
+//!   line
+//! - `cvd`: one `{"type":..,"model":..,"severity":..,"color":..,"simulated":..}`
+//!   object per line
+//! - `textcolor`: one `{"background":..,"text":"black"|"white"}` object per
+//!   line, or `{"background":..,"text":..,"contrast":..}` with `--candidates`
+//!   or `--tint`, where the text is a color
+//! - `list`: one `{"name":..,"color":..}` object per line
+//! - `scale`: one `{"name":..,"color":..}` object per step
+//! - `palette check`: one `{"vision":..,"metric":..,"difference":..,"color1":..,
+//!   "color2":..,"min":..,"pass":..}` object per line, with the closest pair of
+//!   colors for normal vision and each simulated color vision deficiency
+//! - `grep`: one `{"path":..,"line":..,"column":..,"text":..,"color":..}`
+//!   object per line, or `{"count":..,"color":..}` with `--unique`
+//! - `lint`: one `{"path":..,"line":..,"selector":..,"color":..,"background":..,
+//!   "contrast":..,"fix":{"property":..,"color":..}|null}` object per failing
+//!   rule
+//! - `printf`: `{"text":..}`
+//! - `term`: one `{"name":..,"ansi":..}` object per line
+//! - `libs`: `{"name":..,"version":..,"dependencies":..}`
+
+use anyhow::Result;
+use color_space::ToRgb;
+use std::{
+    fmt,
+    io::{stdout, Write},
+};
+
+use crate::color::{hex, Color, ColorFormat, ColorSpace};
+
+/// A JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    /// Prints the value, followed by a line break
+    pub(crate) fn print(&self) -> Result<()> {
+        writeln!(stdout(), "{}", self)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Json::Number(_) => f.write_str("null"),
+            Json::String(s) => write_string(f, s),
+            Json::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            Json::Object(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Json::Bool(b)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(n: f64) -> Self {
+        Json::Number(n)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(n: usize) -> Self {
+        Json::Number(n as f64)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl From<Color> for Json {
+    /// Converts a color to a JSON object containing the color in all
+    /// supported color spaces
+    fn from(color: Color) -> Self {
+        let hex = hex::rgb_to_u32(color.to_rgb());
+
+        let mut entries = vec![
+            ("hex", Json::String(format!("#{:06x}", hex))),
+            ("name", ColorFormat::Html.format(color).into()),
+        ];
+        for &space in ColorSpace::ALL.iter() {
+            let (_, values) = color.to_color_space(space).divide();
+            let components = space
+                .component_names()
+                .iter()
+                .zip(values)
+                .map(|(&name, value)| (name, Json::Number(round(value))))
+                .collect();
+            entries.push((space.name(), Json::Object(components)));
+        }
+        Json::Object(entries)
+    }
+}
+
+/// Rounds to 4 decimal digits, to hide imprecisions caused by conversions
+fn round(n: f64) -> f64 {
+    (n * 10_000.0).round() / 10_000.0 + 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Json;
+
+    #[test]
+    fn test_json_display() {
+        let json = Json::Object(vec![
+            (
+                "a",
+                Json::Array(vec![Json::Number(1.5), Json::Null, true.into()]),
+            ),
+            ("b", "x\"y\n\u{1}".into()),
+            ("c", Json::Number(f64::NAN)),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            r#"{"a":[1.5,null,true],"b":"x\"y\n\u0001","c":null}"#
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{stdout, Write};
+
+use crate::{
+    color::{stylesheet::ColorPair, Color, ColorFormat},
+    terminal::json::Json,
+    State,
+};
+
+/// A rule whose text color doesn't have enough contrast to its background
+pub(crate) struct LintFailure<'a> {
+    pub path: &'a str,
+    pub pair: &'a ColorPair,
+    pub contrast: f64,
+    /// The suggested declaration, e.g. `("color", #767676)`
+    pub fix: Option<(&'static str, Color)>,
+}
+
+/// Prints the rules that don't meet the required contrast, one per line
+pub(crate) fn print_lint_failures(state: State, failures: &[LintFailure<'_>]) -> Result<()> {
+    let mut stdout = stdout();
+
+    for f in failures {
+        let location = format!("{}:{}", f.path, f.pair.line);
+        let fix = match f.fix {
+            Some((property, color)) => {
+                format!("{}: {}", property, ColorFormat::Hex.format_or_hex(color))
+            }
+            None => String::new(),
+        };
+
+        if state.json {
+            let fix = match f.fix {
+                Some((property, color)) => {
+                    Json::Object(vec![("property", property.into()), ("color", color.into())])
+                }
+                None => Json::Null,
+            };
+            Json::Object(vec![
+                ("path", f.path.into()),
+                ("line", f.pair.line.into()),
+                ("selector", f.pair.selector.as_str().into()),
+                ("color", f.pair.color.into()),
+                ("background", f.pair.background.into()),
+                ("contrast", f.contrast.into()),
+                ("fix", fix),
+            ])
+            .print()?;
+        } else if state.color {
+            let fix_text = if fix.is_empty() {
+                String::new()
+            } else {
+                format!("  fix: {}", fix)
+            };
+            writeln!(
+                stdout,
+                " {}{}  {}  {}  {}{}",
+                "██".color(f.pair.color.to_term_color()),
+                "██".color(f.pair.background.to_term_color()),
+                format!("{:5.2}", f.contrast).red(),
+                location.dimmed(),
+                f.pair.selector.bold(),
+                fix_text.dimmed(),
+            )?;
+        } else {
+            writeln!(
+                stdout,
+                "{}\t{}\t{:.2}\t{}",
+                location, f.pair.selector, f.contrast, fix
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -3,7 +3,11 @@ use colored::{Color::TrueColor, Colorize};
 use std::io::{stdout, Write};
 
 use crate::{
-    color::{html::HTML_COLOR_NAMES, space::Rgb, Color, TextColor},
+    color::{
+        alias, format::NumberFormat, html::HTML_COLOR_NAMES, latex, space::Rgb, Color, ColorFormat,
+        TextColor,
+    },
+    terminal::json::Json,
     State,
 };
 
@@ -14,36 +18,67 @@ const WHITE: colored::Color = TrueColor {
 };
 const BLACK: colored::Color = TrueColor { r: 0, g: 0, b: 0 };
 
-pub fn list(state: State) -> Result<()> {
+/// Returns the HTML colors (without aliases such as `magenta`) and the
+/// user-defined colors
+pub fn list_colors() -> impl Iterator<Item = (&'static str, Color)> {
+    let html_colors = HTML_COLOR_NAMES
+        .iter()
+        .filter(|&&(name, _)| !(name == "magenta" || name == "aqua" || name.ends_with("grey")))
+        .map(|&(name, color)| (name, Color::Rgb(Rgb::from_hex(color))));
+    let user_colors = alias::all()
+        .iter()
+        .map(|(name, color)| (name.as_str(), *color));
+
+    html_colors.chain(user_colors)
+}
+
+/// Lists all named colors. If an output format is given, the colors are
+/// printed in that format, one per line.
+pub fn list(state: State, output: Option<ColorFormat>) -> Result<()> {
     let mut stdout = stdout();
 
     let mut even = false;
 
-    for &(name, color) in HTML_COLOR_NAMES {
-        if name == "magenta" || name == "aqua" || name.ends_with("grey") {
+    for (name, color) in list_colors() {
+        if state.json {
+            Json::Object(vec![("name", name.into()), ("color", color.into())]).print()?;
+            continue;
+        }
+        if let Some(output) = output {
+            let line = match output.latex_model() {
+                Some(model) => {
+                    latex::define_named_color(name, color, model, NumberFormat::default())
+                }
+                None => format!("{:22} {}", name, output.format_or_hex(color)),
+            };
+            if state.color {
+                writeln!(stdout, "{}  {}", "██".color(color.to_term_color()), line)?;
+            } else {
+                writeln!(stdout, "{}", line)?;
+            }
             continue;
         }
-
         if !state.color {
             writeln!(stdout, "{}", name)?;
             continue;
         }
 
-        let color = Color::Rgb(Rgb::from_hex(color));
         let term_color = color.to_term_color();
         let text_color = match color.text_color() {
             TextColor::Black => BLACK,
             TextColor::White => WHITE,
         };
 
-        let name = format!(" {}{}", name, &"                      "[name.len()..]);
+        let name = format!(" {:22}", name);
         write!(stdout, "{}", name.color(text_color).on_color(term_color))?;
         if even {
             writeln!(stdout)?;
         }
         even = !even;
     }
-    writeln!(stdout)?;
+    if !state.json && output.is_none() {
+        writeln!(stdout)?;
+    }
 
     Ok(())
 }
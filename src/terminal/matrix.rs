@@ -0,0 +1,59 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{stdout, Write};
+
+use crate::{
+    color::{Color, ColorFormat},
+    State,
+};
+
+/// Prints a square matrix with a value for each pair of colors, where
+/// `values[row][column]` belongs to `colors[row]` and `colors[column]`. The
+/// cells are colored with the color returned by `rating`.
+pub(crate) fn print_matrix(
+    state: State,
+    colors: &[Color],
+    values: &[Vec<f64>],
+    precision: usize,
+    rating: impl Fn(f64) -> colored::Color,
+) -> Result<()> {
+    let mut stdout = stdout();
+    let labels: Vec<String> = colors
+        .iter()
+        .map(|&c| ColorFormat::Hex.format_or_hex(c))
+        .collect();
+
+    if !state.color {
+        writeln!(stdout, "\t{}", labels.join("\t"))?;
+        for (label, row) in labels.iter().zip(values) {
+            let row: Vec<String> = row.iter().map(|v| format!("{:.*}", precision, v)).collect();
+            writeln!(stdout, "{}\t{}", label, row.join("\t"))?;
+        }
+        return Ok(());
+    }
+
+    write!(stdout, "{:11}", "")?;
+    for (&color, label) in colors.iter().zip(&labels) {
+        write!(
+            stdout,
+            "  {} {:7}",
+            "██".color(color.to_term_color()),
+            label
+        )?;
+    }
+    writeln!(stdout)?;
+
+    for (i, ((&color, label), row)) in colors.iter().zip(&labels).zip(values).enumerate() {
+        write!(stdout, " {} {:7}", "██".color(color.to_term_color()), label)?;
+        for (j, &value) in row.iter().enumerate() {
+            if i == j {
+                write!(stdout, "  {:>10}", "–".dimmed())?;
+            } else {
+                let cell = format!("{:>10.*}", precision, value);
+                write!(stdout, "  {}", cell.color(rating(value)))?;
+            }
+        }
+        writeln!(stdout)?;
+    }
+    Ok(())
+}
@@ -1,6 +1,12 @@
+mod grep;
+pub(crate) mod json;
+mod lint;
 mod list;
+mod matrix;
+mod palette;
 mod picker;
 mod printf;
+mod scale;
 mod show;
 pub(crate) mod stdin;
 mod term;
@@ -14,16 +20,22 @@ use std::{
 };
 
 use crate::{
-    color::{Color, ColorFormat},
+    color::{cvd::Simulation, difference::DeltaE, format::NumberFormat, Color, ColorFormat},
+    terminal::json::Json,
     State,
 };
 
-pub(crate) use list::list;
+pub(crate) use grep::{print_color_counts, print_matches};
+pub(crate) use lint::{print_lint_failures, LintFailure};
+pub(crate) use list::{list, list_colors};
+pub(crate) use matrix::print_matrix;
+pub(crate) use palette::{print_palette_checks, PaletteCheck};
 pub(crate) use picker::ColorPicker;
 pub(crate) use printf::print_text;
-pub(crate) use show::show_colors;
+pub(crate) use scale::print_scale;
+pub(crate) use show::{print_template, show_color_pairs, show_colors};
 pub(crate) use term::term_colors;
-pub(crate) use textcolor::text_colors;
+pub(crate) use textcolor::{custom_text_colors, text_colors};
 
 pub(crate) fn compare_colors(
     state: State,
@@ -59,10 +71,46 @@ pub(crate) fn compare_colors(
     Ok(())
 }
 
+/// Prints how well the colors can be distinguished by viewers with a color
+/// vision deficiency, before and after daltonization. Without colors, the
+/// report is printed to stderr, so it doesn't mix with the colors in a pipe.
+pub(crate) fn daltonize_report(
+    state: State,
+    simulation: Simulation,
+    before: &[Color],
+    after: &[Color],
+) -> Result<()> {
+    let closest = |colors: &[Color]| {
+        let simulated: Vec<Color> = colors.iter().map(|&c| simulation.simulate(c)).collect();
+        DeltaE::Ciede2000.closest_pair(&simulated)
+    };
+    if let (Some((_, _, before)), Some((_, _, after))) = (closest(before), closest(after)) {
+        let text = format!(
+            "Minimum ΔE2000 with {}: {:.2} → {:.2}",
+            simulation, before, after,
+        );
+        if state.json {
+            Json::Object(vec![
+                ("simulation", simulation.to_string().into()),
+                ("metric", DeltaE::Ciede2000.name().into()),
+                ("before", before.into()),
+                ("after", after.into()),
+            ])
+            .print()?;
+        } else if state.color {
+            writeln!(io::stdout(), "{}", text.dimmed())?;
+        } else {
+            writeln!(io::stderr(), "{}", text)?;
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn list_small(
     state: State,
     title: Option<&str>,
     colors: impl IntoIterator<Item = (Color, ColorFormat)>,
+    number_format: NumberFormat,
     color_width: usize,
 ) -> Result<()> {
     let mut stdout = io::stdout();
@@ -94,7 +142,11 @@ pub(crate) fn list_small(
         writeln!(stdout)?;
     } else {
         for (color, format) in colors {
-            writeln!(stdout, "{}", format.format_or_hex(color))?;
+            writeln!(
+                stdout,
+                "{}",
+                format.format_or_hex_with(color, number_format)
+            )?;
         }
     }
     Ok(())
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{stdout, Write};
+
+use crate::{
+    color::{difference::DeltaE, format::NumberFormat, Color, ColorFormat},
+    terminal::{self, json::Json},
+    State,
+};
+
+/// The closest pair of colors in a palette, as perceived with normal vision
+/// or with a color vision deficiency
+pub(crate) struct PaletteCheck {
+    /// e.g. "normal vision" or "deuteranopia"
+    pub vision: String,
+    /// The colors as they are perceived
+    pub perceived: Vec<Color>,
+    /// The indices of the closest pair and their difference
+    pub closest: (usize, usize, f64),
+}
+
+/// Prints the perceived palette and its closest pair for each check
+pub(crate) fn print_palette_checks(
+    state: State,
+    palette: &[(Color, ColorFormat)],
+    checks: &[PaletteCheck],
+    metric: DeltaE,
+    min: f64,
+) -> Result<()> {
+    let mut stdout = stdout();
+    // ΔEOK is much smaller than the other metrics
+    let precision = if metric == DeltaE::Ok { 4 } else { 2 };
+
+    for check in checks {
+        let (i, j, difference) = check.closest;
+        let pass = difference >= min;
+        let (color1, format1) = palette[i];
+        let (color2, format2) = palette[j];
+
+        if state.json {
+            Json::Object(vec![
+                ("vision", check.vision.as_str().into()),
+                ("metric", metric.name().into()),
+                ("difference", difference.into()),
+                ("color1", color1.into()),
+                ("color2", color2.into()),
+                ("min", min.into()),
+                ("pass", pass.into()),
+            ])
+            .print()?;
+        } else if state.color {
+            writeln!(stdout, "{}", check.vision.bold())?;
+            terminal::list_small(
+                state,
+                None,
+                check.perceived.iter().map(|&c| (c, ColorFormat::Hex)),
+                NumberFormat::default(),
+                6,
+            )?;
+            let text = format!(
+                "{} {:.*}  {}",
+                metric.name(),
+                precision,
+                difference,
+                if pass { "pass" } else { "fail" },
+            );
+            writeln!(
+                stdout,
+                "{}{}  {}  {}",
+                "███".color(check.perceived[i].to_term_color()),
+                "███".color(check.perceived[j].to_term_color()),
+                if pass { text.green() } else { text.red() },
+                format!(
+                    "{} ↔ {}",
+                    format1.format_or_hex(color1),
+                    format2.format_or_hex(color2),
+                )
+                .dimmed(),
+            )?;
+        } else {
+            writeln!(
+                stdout,
+                "{}\t{:.*}\t{}\t{}\t{}",
+                check.vision,
+                precision,
+                difference,
+                if pass { "pass" } else { "fail" },
+                format1.format_or_hex(color1),
+                format2.format_or_hex(color2),
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -32,6 +32,8 @@ impl ColorPicker {
                 ColorSpace::Xyz => 3,
                 ColorSpace::Yxy => 3,
                 ColorSpace::Gray => 1,
+                ColorSpace::Oklab => 3,
+                ColorSpace::Oklch => 3,
             },
             None => 1,
         };
@@ -26,7 +26,7 @@ impl ThreeLines {
         assert!(!components.is_empty());
         Self {
             name,
-            components: components.iter().copied().collect(),
+            components: components.to_vec(),
             current: 0,
             typing: None,
             from_color,
@@ -200,7 +200,8 @@ impl ThreeLines {
                             comp: ((i + self.len() - 1) % self.len(), i),
                             v_values: (v_value, v_value + (max_v - min_v) / 30.0),
                             bounds: (min_h, max_h),
-                            highlight: Some(v_highlight % 2 == 0).filter(|_| j == v_highlight / 2),
+                            highlight: Some(v_highlight.is_multiple_of(2))
+                                .filter(|_| j == v_highlight / 2),
                         }
                         .write(&mut buf, term_width)?;
                         writeln!(buf)?;
@@ -359,7 +360,7 @@ impl Gradient {
                     TextColor::Black => TermColor::Black,
                     TextColor::White => TermColor::BrightWhite,
                 };
-                if highlighted % 2 == 0 {
+                if highlighted.is_multiple_of(2) {
                     Self::write_char(&mut buf, t, tc2)?;
                 } else {
                     Self::write_char(&mut buf, tc1, t)?;
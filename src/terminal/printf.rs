@@ -2,14 +2,22 @@ use anyhow::{Context, Result};
 use color_space::{Rgb, ToRgb};
 use std::io::{stdout, Write};
 
-use crate::color::{Color, ColorFormat};
+use crate::{
+    color::{Color, ColorFormat},
+    terminal::json::Json,
+    State,
+};
 
-pub(crate) fn print_text(mut text: &str, colors: &[(Color, ColorFormat)]) -> Result<()> {
+pub(crate) fn print_text(
+    state: State,
+    mut text: &str,
+    colors: &[(Color, ColorFormat)],
+) -> Result<()> {
     let mut buf = Vec::with_capacity(text.len() * 3 / 2);
 
     let mut color_idx = 0;
 
-    while let Some(next_index) = text.find(|c| matches!(c, '\\' | '%')) {
+    while let Some(next_index) = text.find(['\\', '%']) {
         if next_index > 0 {
             let (before, after) = text.split_at(next_index);
             buf.extend(before.as_bytes());
@@ -69,6 +77,44 @@ pub(crate) fn print_text(mut text: &str, colors: &[(Color, ColorFormat)]) -> Res
     }
     buf.extend(text.as_bytes());
 
+    if state.json {
+        let text = strip_escape_sequences(&String::from_utf8_lossy(&buf));
+        return Json::Object(vec![("text", text.into())]).print();
+    }
     stdout().write_all(&buf)?;
     Ok(())
 }
+
+/// Removes ANSI escape sequences such as `\x1B[1m`, so the text can be used
+/// without a terminal
+fn strip_escape_sequences(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1B' {
+            result.push(c);
+        } else if chars.as_str().starts_with('[') {
+            // skip the parameters until the final byte, e.g. `m`
+            chars.next();
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_escape_sequences;
+
+    #[test]
+    fn test_strip_escape_sequences() {
+        assert_eq!(
+            strip_escape_sequences("\x1B[1mbold\x1B[m \x1B[38;2;255;0;0mred\x1B[m\x1B"),
+            "bold red"
+        );
+    }
+}
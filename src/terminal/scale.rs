@@ -0,0 +1,53 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{stdout, Write};
+
+use crate::{
+    color::{format::NumberFormat, Color, ColorFormat},
+    terminal::{self, json::Json},
+    State,
+};
+
+/// Prints the steps of a color scale as `name: value` lines. In a terminal,
+/// they're preceded by a row of swatches.
+pub(crate) fn print_scale(
+    state: State,
+    steps: &[(String, Color)],
+    output: ColorFormat,
+    number_format: NumberFormat,
+) -> Result<()> {
+    let mut stdout = stdout();
+
+    if state.color {
+        terminal::list_small(
+            state,
+            None,
+            steps.iter().map(|&(_, c)| (c, output)),
+            number_format,
+            8,
+        )?;
+        writeln!(stdout)?;
+    }
+
+    for (name, color) in steps {
+        let value = output.format_or_hex_with(*color, number_format);
+        if state.json {
+            Json::Object(vec![
+                ("name", name.as_str().into()),
+                ("color", (*color).into()),
+            ])
+            .print()?;
+        } else if state.color {
+            writeln!(
+                stdout,
+                "{}  {}: {}",
+                "██".color(color.to_term_color()),
+                name,
+                value
+            )?;
+        } else {
+            writeln!(stdout, "{}: {}", name, value)?;
+        }
+    }
+    Ok(())
+}
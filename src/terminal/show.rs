@@ -5,97 +5,256 @@ use std::io::{stdout, Stdout, Write};
 use std::iter;
 
 use crate::{
-    color::{format, Color, ColorFormat},
+    color::{format, format::NumberFormat, template::Template, Color, ColorFormat, ColorSpace},
+    terminal::json::Json,
     State,
 };
 
+/// Prints the colors. If `all` is true, the colors are printed in every
+/// supported color space.
 pub fn show_colors(
     state: State,
     colors: impl IntoIterator<Item = Color>,
-    output: ColorFormat,
+    outputs: &[ColorFormat],
+    number_format: NumberFormat,
+    template: Option<&Template>,
     square_size: u32,
+    all: bool,
 ) -> Result<()> {
+    if state.json {
+        for color in colors {
+            Json::from(color).print()?;
+        }
+        return Ok(());
+    }
+    if let Some(template) = template {
+        return print_template(state, colors, template);
+    }
+
     let mut stdout = stdout();
 
+    let formats: Vec<ColorFormat> = if all {
+        let mut formats = outputs.to_vec();
+        let all_formats = iter::once(ColorFormat::Hex)
+            .chain(ColorSpace::ALL.iter().map(|&cs| ColorFormat::Normal(cs)));
+        for format in all_formats {
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        formats
+    } else {
+        outputs.to_vec()
+    };
+
     if state.color && square_size > 0 {
         writeln!(stdout)?;
     }
     for color in colors {
-        show_color(state, &mut stdout, color, output, square_size)?;
+        let shown = Shown {
+            outputs,
+            formats: &formats,
+            number_format,
+            all,
+        };
+        show_color(state, &mut stdout, &[color], shown, square_size)?;
     }
     Ok(())
 }
 
-/// Print a colored square
+/// Prints each pair of colors side by side, e.g. an original and a modified
+/// color, followed by the second color in the output formats. Without colors,
+/// only the second color is printed.
+pub fn show_color_pairs(
+    state: State,
+    pairs: impl IntoIterator<Item = (Color, Color)>,
+    outputs: &[ColorFormat],
+    number_format: NumberFormat,
+    square_size: u32,
+) -> Result<()> {
+    let mut stdout = stdout();
+
+    if state.color && square_size > 0 {
+        writeln!(stdout)?;
+    }
+    for (left, right) in pairs {
+        let shown = Shown {
+            outputs,
+            formats: outputs,
+            number_format,
+            all: false,
+        };
+        show_color(state, &mut stdout, &[left, right], shown, square_size)?;
+    }
+    Ok(())
+}
+
+/// The formats that are printed by `show_color`
+#[derive(Clone, Copy)]
+struct Shown<'a> {
+    /// The output formats, which are highlighted
+    outputs: &'a [ColorFormat],
+    /// The output formats, and all formats if `all` is true
+    formats: &'a [ColorFormat],
+    number_format: NumberFormat,
+    /// Whether only `formats` is printed, instead of the preferred formats
+    all: bool,
+}
+
+impl Shown<'_> {
+    fn format(&self, format: ColorFormat, color: Color) -> Option<ColoredString> {
+        let s = format.format_with(color, self.number_format)?;
+        if self.outputs.contains(&format) {
+            Some(s.bold())
+        } else {
+            Some(s.dimmed())
+        }
+    }
+
+    /// Returns the rows of formats that are printed next to a square of the
+    /// given size
+    fn rows(&self, square_size: u32) -> Vec<Vec<ColorFormat>> {
+        if self.all {
+            let first_row = format::PREFERRED_FORMATS[0];
+            let rest: Vec<ColorFormat> = self
+                .formats
+                .iter()
+                .copied()
+                .filter(|f| !first_row.contains(f))
+                .collect();
+            return iter::once(first_row.to_vec())
+                .chain(rest.chunks(2).map(<[_]>::to_vec))
+                .collect();
+        }
+
+        let rows: Vec<Vec<ColorFormat>> = format::PREFERRED_FORMATS
+            .iter()
+            .take(square_size as usize)
+            .map(|row| row.to_vec())
+            .collect();
+        let missing_in = |rows: &[Vec<ColorFormat>]| -> Vec<ColorFormat> {
+            self.formats
+                .iter()
+                .copied()
+                .filter(|f| matches!(f, ColorFormat::Code(_) | ColorFormat::Css(_)))
+                .filter(|f| !rows.iter().any(|row| row.contains(f)))
+                .collect()
+        };
+
+        // code literals and CSS functions aren't among the preferred formats,
+        // so make sure that they are visible by replacing the last rows
+        let mut keep = rows.len();
+        let mut missing = missing_in(&rows);
+        while keep > 0 && rows.len() - keep < missing.len().div_ceil(2) {
+            keep -= 1;
+            missing = missing_in(&rows[..keep]);
+        }
+        if missing.is_empty() {
+            return rows;
+        }
+        let per_row = missing.len().div_ceil(rows.len() - keep);
+        let mut rows = rows;
+        rows.truncate(keep);
+        rows.extend(missing.chunks(per_row).map(<[_]>::to_vec));
+        rows
+    }
+}
+
+/// Prints each color formatted with the template, one per line. If colors are
+/// enabled, a tiny square is printed in front of it.
+pub fn print_template(
+    state: State,
+    colors: impl IntoIterator<Item = Color>,
+    template: &Template,
+) -> Result<()> {
+    let mut stdout = stdout();
+
+    for color in colors {
+        let text = template.render(color);
+        if state.color {
+            writeln!(
+                stdout,
+                "{}  {}",
+                make_tiny_square().color(color.to_term_color()),
+                text
+            )?;
+        } else {
+            writeln!(stdout, "{}", text)?;
+        }
+    }
+    Ok(())
+}
+
+/// Print a colored square for each color, side by side. The formats of the
+/// last color are printed on the right.
 fn show_color(
     state: State,
     stdout: &mut Stdout,
-    color: Color,
-    output: ColorFormat,
+    colors: &[Color],
+    shown: Shown<'_>,
     square_size: u32,
 ) -> Result<()> {
-    let color = match output {
-        ColorFormat::Normal(s) => color.to_color_space(s),
-        ColorFormat::Hex | ColorFormat::Html => color,
-    };
-    let rgb = color.to_rgb();
-
-    let term_color = colored::Color::TrueColor {
-        r: rgb.r.round() as u8,
-        g: rgb.g.round() as u8,
-        b: rgb.b.round() as u8,
-    };
+    let color = *colors.last().unwrap();
+    let term_colors: Vec<colored::Color> = colors
+        .iter()
+        .map(|c| {
+            let rgb = c.to_rgb();
+            colored::Color::TrueColor {
+                r: rgb.r.round() as u8,
+                g: rgb.g.round() as u8,
+                b: rgb.b.round() as u8,
+            }
+        })
+        .collect();
 
     if !state.color {
-        let color = output.format_or_hex(color);
+        let formats: Vec<String> = shown
+            .formats
+            .iter()
+            .map(|f| f.format_or_hex_with(color, shown.number_format))
+            .collect();
 
-        writeln!(stdout, "{}", color)?;
+        writeln!(stdout, "{}", formats.join("\t"))?;
     } else if square_size >= 1 {
-        let formats = format::PREFERRED_FORMATS
+        let rows = shown.rows(square_size);
+        let formats = rows
             .iter()
-            .take(square_size as usize)
-            .map(|&c| {
-                c.iter().copied().flat_map(|c| {
-                    Some((
-                        c,
-                        if c != output {
-                            c.format(color).map(|s| s.dimmed())?
-                        } else {
-                            c.format(color).map(|s| s.bold())?
-                        },
-                    ))
-                })
+            .map(|row| {
+                row.iter()
+                    .flat_map(move |&c| Some((c, shown.format(c, color)?)))
             })
             .map(Some)
-            .chain(iter::once(None).cycle());
+            .chain(iter::once(None).cycle())
+            .take(rows.len().max(square_size as usize));
 
-        print_color(stdout, term_color, &make_square(square_size), formats, true)?;
+        print_color(
+            stdout,
+            &term_colors,
+            &make_square(square_size),
+            formats,
+            true,
+        )?;
         if square_size > 0 {
             writeln!(stdout)?;
         }
     } else {
-        let formats = iter::once(output)
+        let count = shown.formats.len().max(3);
+        let formats = shown
+            .formats
+            .iter()
+            .copied()
             .chain(
                 format::PREFERRED_FORMATS_SHORT
                     .iter()
                     .copied()
-                    .filter(|&f| f != output),
+                    .filter(|f| !shown.formats.contains(f)),
             )
-            .filter_map(|c| {
-                Some((
-                    c,
-                    if c != output {
-                        c.format(color).map(|s| s.dimmed())?
-                    } else {
-                        c.format(color).map(|s| s.bold())?
-                    },
-                ))
-            })
-            .take(3);
+            .filter_map(|c| Some((c, shown.format(c, color)?)))
+            .take(count);
 
         print_color(
             stdout,
-            term_color,
+            &term_colors,
             make_tiny_square(),
             iter::once(Some(formats)),
             false,
@@ -108,10 +267,10 @@ fn show_color(
     Ok(())
 }
 
-/// Prints the color square and the color formats on its right
+/// Prints the color squares and the color formats on their right
 fn print_color<I, J>(
     stdout: &mut Stdout,
-    term_color: colored::Color,
+    term_colors: &[colored::Color],
     square: &str,
     formats: I,
     add_padding: bool,
@@ -120,9 +279,18 @@ where
     I: Iterator<Item = Option<J>>,
     J: Iterator<Item = (ColorFormat, ColoredString)>,
 {
-    for (line, colors) in square.lines().zip(formats) {
-        // Print one line of the square
-        write!(stdout, "{}", line.color(term_color))?;
+    let width = square.lines().next().map_or(0, |l| l.chars().count());
+    let blank = " ".repeat(width);
+    let lines = square.lines().chain(iter::repeat(blank.as_str()));
+
+    for (line, colors) in lines.zip(formats) {
+        // Print one line of the squares
+        for (i, &term_color) in term_colors.iter().enumerate() {
+            if i > 0 && !line.starts_with(' ') {
+                write!(stdout, " ")?;
+            }
+            write!(stdout, "{}", line.color(term_color))?;
+        }
 
         // Print one line of the color formats
         if let Some(colors) = colors {
@@ -133,13 +301,13 @@ where
                         write!(stdout, "  {}", col)?;
                         step += 1;
                     }
-                    ColorFormat::Html => {
-                        write!(stdout, "  {:16}", col)?;
+                    ColorFormat::Html | ColorFormat::NearestName => {
+                        write!(stdout, "  {:20}", col)?;
                         step += 1;
                     }
                     _ => {
                         if add_padding && step == 1 {
-                            write!(stdout, "                  ")?;
+                            write!(stdout, "{:22}", "")?;
                         }
                         write!(stdout, "  {:25}", col)?;
                     }
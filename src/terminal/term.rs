@@ -5,7 +5,9 @@ use colored::{
 };
 use std::io::{stdout, Stdout, Write};
 
-pub fn term_colors() -> Result<()> {
+use crate::{terminal::json::Json, State};
+
+pub fn term_colors(state: State) -> Result<()> {
     let mut stdout = stdout();
 
     let colors = &[
@@ -27,6 +29,17 @@ pub fn term_colors() -> Result<()> {
         colored::Color::Magenta,
     ];
 
+    if state.json {
+        for color in colors {
+            Json::Object(vec![
+                ("name", format!("{:?}", color).into()),
+                ("ansi", color.to_fg_str().as_ref().into()),
+            ])
+            .print()?;
+        }
+        return Ok(());
+    }
+
     writeln!(
         stdout,
         "The appearance of these colors depends on your terminal.\n"
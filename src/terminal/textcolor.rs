@@ -3,7 +3,8 @@ use colored::Colorize;
 use std::io::{stdout, Write};
 
 use crate::{
-    color::{self, Color},
+    color::{self, Algorithm, Color, ColorFormat},
+    terminal::json::Json,
     State,
 };
 
@@ -20,7 +21,13 @@ pub fn text_colors(state: State, colors: impl IntoIterator<Item = Color>) -> Res
             color::TextColor::White => (white, "white"),
         };
 
-        if state.color {
+        if state.json {
+            Json::Object(vec![
+                ("background", color.into()),
+                ("text", other_name.into()),
+            ])
+            .print()?;
+        } else if state.color {
             let color_block = format!("  {}  ", other_name)
                 .color(other.to_term_color())
                 .on_color(color.to_term_color());
@@ -32,3 +39,39 @@ pub fn text_colors(state: State, colors: impl IntoIterator<Item = Color>) -> Res
     }
     Ok(())
 }
+
+/// Prints the given text color for each background color, with their contrast
+pub fn custom_text_colors(
+    state: State,
+    pairs: impl IntoIterator<Item = (Color, Color, ColorFormat)>,
+) -> Result<()> {
+    let mut stdout = stdout();
+
+    for (background, text, output) in pairs {
+        let contrast = Algorithm::Wcag.contrast(text, background);
+        let value = output.format_or_hex(text);
+
+        if state.json {
+            Json::Object(vec![
+                ("background", background.into()),
+                ("text", text.into()),
+                ("contrast", contrast.into()),
+            ])
+            .print()?;
+        } else if state.color {
+            let color_block = format!("  {}  ", value)
+                .color(text.to_term_color())
+                .on_color(background.to_term_color());
+
+            writeln!(
+                stdout,
+                "{}  {}",
+                color_block,
+                format!("(contrast: {:.2})", contrast).dimmed()
+            )?;
+        } else {
+            writeln!(stdout, "{}", value)?;
+        }
+    }
+    Ok(())
+}